@@ -0,0 +1,291 @@
+//! Host side of the light-client subsystem: pull a `LightClientUpdate` and the
+//! trusted sync committee from a beacon REST API, prove them in the
+//! light-client guest, and return the trustlessly derived execution state root.
+
+use anyhow::{anyhow, Result};
+use methods::guest::{LIGHTCLIENT_ELF, LIGHTCLIENT_ID};
+use methods::light_client::{
+    BeaconBlockHeader, ExecutionPayloadHeader, LightClientInputs, LightClientOutputs,
+    LightClientUpdate, SyncAggregate, SyncCommittee, SLOTS_PER_SYNC_COMMITTEE_PERIOD,
+};
+use methods::u256::U256;
+use reqwest::blocking::Client;
+use risc0_zkvm::{default_prover, ExecutorEnv};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Execution state root derived from a beacon-chain checkpoint, paired with the
+/// execution block it belongs to. MPT proofs must be fetched at `block_number`
+/// — `state_root` only authenticates the trie at that exact block.
+pub struct Checkpoint {
+    pub state_root: [u8; 32],
+    pub block_number: u64,
+}
+
+/// Fetch the latest finality update + matching sync-committee bootstrap, prove
+/// them, and return the derived execution checkpoint.
+///
+/// `trusted_committee_root` is the digest (sha256 of the pubkeys followed by
+/// the aggregate pubkey, matching `committee_hash` below) of a sync committee
+/// obtained out of band — e.g. from a weak-subjectivity checkpoint pinned by
+/// the caller — NOT fetched from `api`.
+/// The bootstrap endpoint below is keyed on the very update it is meant to
+/// authenticate, so a malicious `api` could otherwise serve a self-consistent
+/// committee alongside a forged update; checking the fetched committee against
+/// this pinned root is what makes the committee a trusted input rather than
+/// something the host takes on the API's word.
+pub fn derive_state_root(
+    client: &Client,
+    api: &str,
+    fork_version: [u8; 4],
+    trusted_committee_root: [u8; 32],
+) -> Result<Checkpoint> {
+    let genesis_validators_root = fetch_genesis_validators_root(client, api)?;
+
+    let update_json = get(client, &format!("{api}/eth/v1/beacon/light_client/finality_update"))?;
+    let data = &update_json["data"];
+    let update = parse_update(data, fork_version, genesis_validators_root)?;
+
+    // The signing committee is the one for the period containing
+    // `signature_slot`. The bootstrap endpoint serves the committee anchored at
+    // a block root, which is the attested header's period; if that period
+    // differs from the signature slot's (i.e. the update straddles a period
+    // boundary) the bootstrap would hand back the wrong committee, so refuse
+    // rather than prove against it.
+    let attested_period = update.attested_header.slot / SLOTS_PER_SYNC_COMMITTEE_PERIOD;
+    let signature_period = update.signature_slot / SLOTS_PER_SYNC_COMMITTEE_PERIOD;
+    if attested_period != signature_period {
+        return Err(anyhow!(
+            "update straddles a sync-committee period boundary (attested {attested_period}, \
+             signature {signature_period}); fetch the committee for the signature period"
+        ));
+    }
+
+    // The sync committee is fetched from a bootstrap anchored at the attested
+    // header's block root. That root comes from the same `api`, so the result
+    // is checked against the pinned committee below. The bootstrap endpoint
+    // keys on the beacon *block root* (hash_tree_root of the header), not its
+    // body root.
+    let block_root = header_root(&update.attested_header);
+    let sync_committee = fetch_bootstrap_committee(client, api, &block_root)?;
+    let committee_hash = committee_hash(&sync_committee);
+
+    // Reject the bootstrap outright if it doesn't match the caller's pinned
+    // committee; otherwise `api` would be trusted for exactly the thing this
+    // subsystem exists to eliminate.
+    if committee_hash != trusted_committee_root {
+        return Err(anyhow!(
+            "beacon API served a sync committee ({}) that does not match the \
+             pinned trusted committee ({}); refusing to prove against it",
+            hex::encode(committee_hash),
+            hex::encode(trusted_committee_root)
+        ));
+    }
+
+    let pubin = LightClientInputs { sync_committee };
+    let env = ExecutorEnv::builder()
+        .write(&pubin)?
+        .write(&update)?
+        .build()?;
+    let receipt = default_prover().prove_elf(env, LIGHTCLIENT_ELF)?;
+    receipt.verify(LIGHTCLIENT_ID)?;
+    let out: LightClientOutputs = receipt.journal.decode()?;
+
+    // The journal pins the committee the guest actually verified against; make
+    // sure it is the one we fetched for the signature slot's period.
+    if out.committee_hash != committee_hash {
+        return Err(anyhow!("proof used an unexpected sync committee"));
+    }
+    if out.signature_slot != update.signature_slot {
+        return Err(anyhow!("proof committed to an unexpected signature slot"));
+    }
+    Ok(Checkpoint { state_root: out.state_root, block_number: out.block_number })
+}
+
+fn parse_update(
+    data: &Value,
+    fork_version: [u8; 4],
+    genesis_validators_root: [u8; 32],
+) -> Result<LightClientUpdate> {
+    let attested = parse_header(&data["attested_header"]["beacon"])?;
+    let finalized = parse_header(&data["finalized_header"]["beacon"])?;
+    let finality_branch = parse_branch(&data["finality_branch"])?;
+    let sync = &data["sync_aggregate"];
+    let sync_aggregate = SyncAggregate {
+        sync_committee_bits: decode_hex(sync["sync_committee_bits"].as_str()
+            .ok_or_else(|| anyhow!("missing sync_committee_bits"))?)?,
+        sync_committee_signature: hex_n::<96>(sync["sync_committee_signature"].as_str()
+            .ok_or_else(|| anyhow!("missing sync signature"))?)?,
+    };
+    let signature_slot = parse_u64(&data["signature_slot"])?;
+
+    // Capella+ light-client headers carry the full execution payload header and
+    // a branch rooting it in the beacon block body.
+    let execution_payload_header = parse_execution_header(&data["finalized_header"]["execution"])?;
+    let execution_branch = parse_branch(&data["finalized_header"]["execution_branch"])?;
+
+    Ok(LightClientUpdate {
+        attested_header: attested,
+        finalized_header: finalized,
+        finality_branch,
+        sync_aggregate,
+        signature_slot,
+        fork_version,
+        genesis_validators_root,
+        execution_payload_header,
+        execution_branch,
+    })
+}
+
+fn parse_execution_header(v: &Value) -> Result<ExecutionPayloadHeader> {
+    let logs_bloom = decode_hex(v["logs_bloom"].as_str().ok_or_else(|| anyhow!("logs_bloom"))?)?;
+    if logs_bloom.len() != 256 {
+        return Err(anyhow!("logs_bloom must be 256 bytes"));
+    }
+    let extra_data = decode_hex(v["extra_data"].as_str().ok_or_else(|| anyhow!("extra_data"))?)?;
+    Ok(ExecutionPayloadHeader {
+        parent_hash: hex32(v["parent_hash"].as_str().ok_or_else(|| anyhow!("parent_hash"))?)?,
+        fee_recipient: hex_n::<20>(v["fee_recipient"].as_str().ok_or_else(|| anyhow!("fee_recipient"))?)?,
+        state_root: hex32(v["state_root"].as_str().ok_or_else(|| anyhow!("state_root"))?)?,
+        receipts_root: hex32(v["receipts_root"].as_str().ok_or_else(|| anyhow!("receipts_root"))?)?,
+        logs_bloom,
+        prev_randao: hex32(v["prev_randao"].as_str().ok_or_else(|| anyhow!("prev_randao"))?)?,
+        block_number: parse_u64(&v["block_number"])?,
+        gas_limit: parse_u64(&v["gas_limit"])?,
+        gas_used: parse_u64(&v["gas_used"])?,
+        timestamp: parse_u64(&v["timestamp"])?,
+        extra_data,
+        base_fee_per_gas: u256_le(&v["base_fee_per_gas"])?,
+        block_hash: hex32(v["block_hash"].as_str().ok_or_else(|| anyhow!("block_hash"))?)?,
+        transactions_root: hex32(v["transactions_root"].as_str().ok_or_else(|| anyhow!("transactions_root"))?)?,
+        withdrawals_root: hex32(v["withdrawals_root"].as_str().ok_or_else(|| anyhow!("withdrawals_root"))?)?,
+        blob_gas_used: parse_u64(&v["blob_gas_used"])?,
+        excess_blob_gas: parse_u64(&v["excess_blob_gas"])?,
+    })
+}
+
+fn parse_header(v: &Value) -> Result<BeaconBlockHeader> {
+    Ok(BeaconBlockHeader {
+        slot: parse_u64(&v["slot"])?,
+        proposer_index: parse_u64(&v["proposer_index"])?,
+        parent_root: hex32(v["parent_root"].as_str().ok_or_else(|| anyhow!("parent_root"))?)?,
+        state_root: hex32(v["state_root"].as_str().ok_or_else(|| anyhow!("state_root"))?)?,
+        body_root: hex32(v["body_root"].as_str().ok_or_else(|| anyhow!("body_root"))?)?,
+    })
+}
+
+fn fetch_genesis_validators_root(client: &Client, api: &str) -> Result<[u8; 32]> {
+    let v = get(client, &format!("{api}/eth/v1/beacon/genesis"))?;
+    hex32(v["data"]["genesis_validators_root"].as_str()
+        .ok_or_else(|| anyhow!("missing genesis_validators_root"))?)
+}
+
+fn fetch_bootstrap_committee(client: &Client, api: &str, block_root: &[u8; 32]) -> Result<SyncCommittee> {
+    let root_hex = format!("0x{}", hex::encode(block_root));
+    let v = get(client, &format!("{api}/eth/v1/beacon/light_client/bootstrap/{root_hex}"))?;
+    let committee = &v["data"]["current_sync_committee"];
+    let pubkeys = committee["pubkeys"].as_array()
+        .ok_or_else(|| anyhow!("missing pubkeys"))?
+        .iter()
+        .map(|p| hex_n::<48>(p.as_str().ok_or_else(|| anyhow!("pubkey not a string"))?))
+        .collect::<Result<_>>()?;
+    let aggregate_pubkey = hex_n::<48>(committee["aggregate_pubkey"].as_str()
+        .ok_or_else(|| anyhow!("missing aggregate_pubkey"))?)?;
+    Ok(SyncCommittee { pubkeys, aggregate_pubkey })
+}
+
+fn get(client: &Client, url: &str) -> Result<Value> {
+    Ok(client.get(url).send()?.error_for_status()?.json::<Value>()?)
+}
+
+fn parse_branch(v: &Value) -> Result<Vec<[u8; 32]>> {
+    v.as_array().ok_or_else(|| anyhow!("branch not an array"))?
+        .iter()
+        .map(|n| hex32(n.as_str().ok_or_else(|| anyhow!("branch node not a string"))?))
+        .collect()
+}
+
+/// Beacon API numeric fields are JSON strings.
+fn parse_u64(v: &Value) -> Result<u64> {
+    Ok(v.as_str().ok_or_else(|| anyhow!("expected numeric string"))?.parse()?)
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    Ok(hex::decode(s.trim_start_matches("0x"))?)
+}
+
+fn hex32(s: &str) -> Result<[u8; 32]> {
+    hex_n::<32>(s)
+}
+
+fn hex_n<const N: usize>(s: &str) -> Result<[u8; N]> {
+    let bytes = decode_hex(s)?;
+    if bytes.len() != N {
+        return Err(anyhow!("expected {N} bytes, got {}", bytes.len()));
+    }
+    let mut out = [0u8; N];
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Parse a decimal `uint256` JSON string into its little-endian SSZ leaf.
+fn u256_le(v: &Value) -> Result<[u8; 32]> {
+    let s = v.as_str().ok_or_else(|| anyhow!("expected numeric string"))?;
+    let mut bytes = U256::from_dec_str(s)
+        .ok_or_else(|| anyhow!("invalid uint256"))?
+        .to_be_bytes();
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// `hash_tree_root(BeaconBlockHeader)`, mirroring the guest's `ssz::header_root`
+/// so the host can derive the beacon block root the bootstrap endpoint keys on.
+fn header_root(h: &BeaconBlockHeader) -> [u8; 32] {
+    merkleize(vec![
+        u64_leaf(h.slot),
+        u64_leaf(h.proposer_index),
+        h.parent_root,
+        h.state_root,
+        h.body_root,
+    ])
+}
+
+/// Mirror of the guest's `ssz::sync_committee_hash`: sha256 of the pubkeys in
+/// order followed by the aggregate pubkey.
+fn committee_hash(committee: &SyncCommittee) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for pk in &committee.pubkeys {
+        hasher.update(pk);
+    }
+    hasher.update(committee.aggregate_pubkey);
+    hasher.finalize().into()
+}
+
+fn u64_leaf(value: u64) -> [u8; 32] {
+    let mut leaf = [0u8; 32];
+    leaf[0..8].copy_from_slice(&value.to_le_bytes());
+    leaf
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn merkleize(mut leaves: Vec<[u8; 32]>) -> [u8; 32] {
+    let mut width = 1usize;
+    while width < leaves.len() {
+        width <<= 1;
+    }
+    leaves.resize(width, [0u8; 32]);
+    while leaves.len() > 1 {
+        let mut next = Vec::with_capacity(leaves.len() / 2);
+        for pair in leaves.chunks(2) {
+            next.push(hash_pair(&pair[0], &pair[1]));
+        }
+        leaves = next;
+    }
+    leaves[0]
+}
@@ -1,11 +1,16 @@
 use anyhow::{anyhow, bail, Context, Result};
 use clap::Parser;
+use methods::u256::U256;
 use methods::{PublicInputs, PublicOutputs, METHOD_ELF, METHOD_ID};
+
+mod beacon;
 use reqwest::blocking::Client;
 use reqwest::header::CONTENT_TYPE;
 use risc0_zkvm::{default_prover, ExecutorEnv};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use std::{fs, path::PathBuf};
 
 #[derive(Parser, Debug)]
@@ -34,10 +39,35 @@ struct Args {
     #[arg(long)]
     calldata: PathBuf,
 
-    /// Threshold as decimal u128 (e.g., 1000000000000000000 for 1e18)
+    /// Threshold as a decimal 256-bit integer (e.g., 1000000000000000000 for 1e18)
     #[arg(long)]
     threshold: String,
 
+    /// Storage slot index of the ERC-20 `balances` mapping on the asset
+    #[arg(long, default_value_t = 0)]
+    balances_slot: u64,
+
+    /// Optional beacon REST API; when set, the pre-block state root is derived
+    /// trustlessly from a sync-committee checkpoint instead of the RPC header.
+    /// `--block-pre` (or the `--tx`-derived pre block) must equal the
+    /// checkpoint's execution block, since MPT proofs are only valid against
+    /// the block they were fetched at. `post_state_root` still comes from
+    /// `--rpc` regardless.
+    #[arg(long)]
+    beacon_api: Option<String>,
+
+    /// Digest of the sync committee to trust, obtained out of band (e.g. from
+    /// a weak-subjectivity checkpoint), NOT from `--beacon-api`: sha256 of the
+    /// 512 committee pubkeys followed by the aggregate pubkey. Required with
+    /// `--beacon-api`, since the bootstrap endpoint is otherwise trusted for
+    /// exactly the RPC-state-root assumption this subsystem exists to remove.
+    #[arg(long)]
+    trusted_committee_root: Option<String>,
+
+    /// Fork version (0x + 8 hex) for the beacon signature domain
+    #[arg(long, default_value = "0x04000000")]
+    fork_version: String,
+
     /// Optional: write journal.json here
     #[arg(long)]
     out: Option<PathBuf>,
@@ -48,7 +78,7 @@ fn main() -> Result<()> {
     let client = Client::new();
 
     // ----------------- Resolve blocks -----------------
-    let (pre_block, post_block) = if let Some(tx) = args.tx.as_ref() {
+    let (mut pre_block, post_block) = if let Some(tx) = args.tx.as_ref() {
         let block = get_tx_block_number(&client, &args.rpc, tx)?;
         if block == 0 { bail!("tx in block 0; cannot set pre = -1"); }
         (block - 1, block)
@@ -58,17 +88,66 @@ fn main() -> Result<()> {
         (pre, post)
     };
 
-    // ----------------- Fetch balances at pre/post -----------------
-    let pre_balance = erc20_balance_of_at(&client, &args.rpc, &args.asset, &args.target, pre_block)
-        .with_context(|| "fetching pre balance")?;
-    let post_balance = erc20_balance_of_at(&client, &args.rpc, &args.asset, &args.target, post_block)
-        .with_context(|| "fetching post balance")?;
+    // ----------------- Resolve the state roots we prove against -----------------
+    // The beacon checkpoint only authenticates the trie at the execution block
+    // it anchors; the MPT proofs below MUST be fetched at that same block, so a
+    // `pre_block` that differs from the checkpoint's block is rejected.
+    let state_root = if let Some(api) = args.beacon_api.as_ref() {
+        let fork_version = parse_fork_version(&args.fork_version)?;
+        let trusted_committee_root = hex32(
+            args.trusted_committee_root.as_deref()
+                .ok_or_else(|| anyhow!("--trusted-committee-root is required with --beacon-api"))?,
+        )?;
+        let checkpoint = beacon::derive_state_root(&client, api, fork_version, trusted_committee_root)
+            .with_context(|| "deriving state root from beacon checkpoint")?;
+        if pre_block != checkpoint.block_number {
+            bail!(
+                "--beacon-api anchors execution block {}, but pre_block resolved to {pre_block}; \
+                 rerun with --block-pre {} (and --tx/--block-post adjusted accordingly)",
+                checkpoint.block_number, checkpoint.block_number
+            );
+        }
+        pre_block = checkpoint.block_number;
+        checkpoint.state_root
+    } else {
+        get_state_root(&client, &args.rpc, pre_block)?
+    };
+    // NOTE: `post_state_root` is always read from `--rpc`, even on the
+    // `--beacon-api` path — the light-client subsystem only derives a single
+    // (pre) checkpoint, so a lying RPC can still move the post root. The
+    // committed loss is bounded by the in-guest EVM execution, not by this
+    // value; see the guest's EVM-execution comment.
+    let post_state_root = get_state_root(&client, &args.rpc, post_block)?;
+
+    // ----------------- Fetch Merkle-Patricia proofs -----------------
+    // The guest re-derives pre/post balances from these proofs; the RPC's
+    // `eth_call` result is no longer used.
+    let slot_key = balance_slot_key(&args.target, args.balances_slot)?;
+    let target_account_pre = get_proof(&client, &args.rpc, &args.target, &[], pre_block)?;
+    let asset_pre = get_proof(&client, &args.rpc, &args.asset, &[slot_key], pre_block)?;
+    let asset_post = get_proof(&client, &args.rpc, &args.asset, &[slot_key], post_block)?;
+
+    let pre_balance = left_pad_be(&asset_pre.storage_values[0]);
+    let post_balance = left_pad_be(&asset_post.storage_values[0]);
+
+    let proofs = StateWitness {
+        target_account_pre: target_account_pre.account_proof,
+        asset_account_pre: asset_pre.account_proof,
+        asset_account_post: asset_post.account_proof,
+        balance_storage_pre: asset_pre.storage_proofs[0].clone(),
+        balance_storage_post: asset_post.storage_proofs[0].clone(),
+        // Parallel to `holders`/`storage` below; empty by default (a PoC that
+        // seeds extra state supplies the matching eth_getProof entries).
+        holder_storage: Vec::new(),
+        target_storage: Vec::new(),
+    };
 
     // ----------------- Fetch code at pre-block -----------------
     let target_code = get_code_at(&client, &args.rpc, &args.target, pre_block)?;
     let asset_code = get_code_at(&client, &args.rpc, &args.asset, pre_block)?;
-    let target_sha: [u8; 32] = Sha256::digest(&target_code).into();
-    let asset_sha: [u8; 32] = Sha256::digest(&asset_code).into();
+    // Ethereum commits keccak256(code) as the account `codeHash`, so bind that.
+    let target_code_hash: [u8; 32] = Keccak256::digest(&target_code).into();
+    let asset_code_hash: [u8; 32] = Keccak256::digest(&asset_code).into();
 
     // ----------------- Read calldata file -----------------
     let calldata_bytes = read_calldata(&args.calldata)?;
@@ -83,22 +162,34 @@ fn main() -> Result<()> {
     pre_post[0..32].copy_from_slice(&pre_balance);
     pre_post[32..64].copy_from_slice(&post_balance);
 
+    // Extra ERC-20 holder balances and target storage the exploit touches.
+    // The guest seeds its EVM with the MPT-proven target balance; these carry
+    // any additional state the PoC reads (e.g. an attacker account or a
+    // reentrancy guard slot). Empty by default — a PoC supplies what it needs.
+    let holders: Vec<([u8; 20], [u8; 32])> = Vec::new();
+    let storage: Vec<([u8; 32], [u8; 32])> = Vec::new();
+
     // Compute the commitment exactly like the guest does
-    let commitment = commit_all_host(&pre_post, &calldata_bytes, &target_code, &asset_code);
+    let commitment = commit_all_host(&pre_post, &calldata_bytes, &target_code, &asset_code, &holders, &storage);
 
     // ----------------- Public inputs -----------------
     let asset20 = addr_to_20(&args.asset)?;
     let target20 = addr_to_20(&args.target)?;
-    let threshold: u128 = args.threshold.parse().context("threshold must be decimal u128")?;
+    let threshold = U256::from_dec_str(&args.threshold)
+        .ok_or_else(|| anyhow!("threshold must be a decimal 256-bit integer"))?
+        .to_be_bytes();
 
     let pubin = PublicInputs {
         threshold,
         commitment,
+        state_root,
+        post_state_root,
         asset: asset20,
         target: target20,
         selector,
-        target_code_sha256: target_sha,
-        asset_code_sha256: asset_sha,
+        balances_slot: args.balances_slot,
+        target_code_hash,
+        asset_code_hash,
     };
 
     // ----------------- Prove in zkVM -----------------
@@ -108,6 +199,9 @@ fn main() -> Result<()> {
         .write(&calldata_bytes)?
         .write(&target_code)?
         .write(&asset_code)?
+        .write(&proofs)?
+        .write(&holders)?
+        .write(&storage)?
         .build()?;
     let prover = default_prover();
     let receipt = prover.prove_elf(env, METHOD_ELF)?;
@@ -123,7 +217,7 @@ fn main() -> Result<()> {
     println!("• asset      = {}", &args.asset);
     println!("• target     = {}", &args.target);
     println!("• selector   = 0x{}", hex::encode(journal.selector));
-    println!("• threshold  = {}", journal.threshold);
+    println!("• threshold  = 0x{}", hex::encode(journal.threshold));
     println!("• loss       = {loss_hex}");
     println!("• loss ≥ thr = {}", journal.loss_ge_threshold);
 
@@ -154,39 +248,107 @@ fn get_tx_block_number(client: &Client, rpc: &str, tx_hash: &str) -> Result<u64>
     Ok(u64::from_str_radix(hexnum.trim_start_matches("0x"), 16)?)
 }
 
-fn erc20_balance_of_at(
+/// Merkle-Patricia witness handed to the guest; field order and names mirror
+/// the `StateWitness` the guest deserializes.
+#[derive(Serialize, Deserialize)]
+struct StateWitness {
+    target_account_pre: Vec<Vec<u8>>,
+    asset_account_pre: Vec<Vec<u8>>,
+    asset_account_post: Vec<Vec<u8>>,
+    balance_storage_pre: Vec<Vec<u8>>,
+    balance_storage_post: Vec<Vec<u8>>,
+    holder_storage: Vec<Vec<Vec<u8>>>,
+    target_storage: Vec<Vec<Vec<u8>>>,
+}
+
+/// Decoded `eth_getProof` response: the account proof plus one storage proof
+/// (and its value) per requested slot.
+struct ProofResult {
+    account_proof: Vec<Vec<u8>>,
+    storage_proofs: Vec<Vec<Vec<u8>>>,
+    storage_values: Vec<Vec<u8>>,
+}
+
+fn get_state_root(client: &Client, rpc: &str, block: u64) -> Result<[u8; 32]> {
+    let res = client.post(rpc).header(CONTENT_TYPE, "application/json")
+        .json(&json!({
+            "jsonrpc": "2.0", "id": 1, "method": "eth_getBlockByNumber",
+            "params": [format!("0x{:x}", block), false]
+        })).send()?.error_for_status()?.json::<serde_json::Value>()?;
+    let root = res["result"]["stateRoot"].as_str()
+        .ok_or_else(|| anyhow!("no stateRoot for block {block}"))?;
+    let bytes = hex::decode(root.trim_start_matches("0x"))?;
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Fetch `eth_getProof` for `addr` at `block`, returning the hex-decoded
+/// (still RLP-encoded) account and storage proof node lists for the requested
+/// `slots`.
+fn get_proof(
     client: &Client,
     rpc: &str,
-    asset: &str,
-    target: &str,
+    addr: &str,
+    slots: &[[u8; 32]],
     block: u64,
-) -> Result<[u8; 32]> {
-    const SEL: &str = "70a08231"; // balanceOf(address)
-    let addr = target.trim_start_matches("0x");
-    let calldata = format!("0x{}{}", SEL, left_pad_32(addr)?);
-    let block_hex = format!("0x{:x}", block);
-
-    let req = json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "eth_call",
-        "params": [
-            { "to": asset, "data": calldata },
-            block_hex
-        ]
-    });
-
+) -> Result<ProofResult> {
+    let slot_params: Vec<String> = slots.iter().map(|s| format!("0x{}", hex::encode(s))).collect();
     let res = client.post(rpc).header(CONTENT_TYPE, "application/json")
-        .json(&req).send()?.error_for_status()?.json::<serde_json::Value>()?;
+        .json(&json!({
+            "jsonrpc": "2.0", "id": 1, "method": "eth_getProof",
+            "params": [addr, slot_params, format!("0x{:x}", block)]
+        })).send()?.error_for_status()?.json::<serde_json::Value>()?;
+    let result = &res["result"];
 
-    let data = res["result"].as_str().ok_or_else(|| anyhow!("missing eth_call result"))?;
-    let bytes = hex::decode(data.trim_start_matches("0x"))?;
-    if bytes.len() != 32 {
-        bail!("eth_call returned {} bytes, expected 32", bytes.len());
+    let account_proof = decode_proof_nodes(&result["accountProof"])?;
+    let mut storage_proofs = Vec::with_capacity(slots.len());
+    let mut storage_values = Vec::with_capacity(slots.len());
+    let storage = result["storageProof"].as_array()
+        .ok_or_else(|| anyhow!("no storageProof in eth_getProof"))?;
+    for entry in storage {
+        storage_proofs.push(decode_proof_nodes(&entry["proof"])?);
+        let value = entry["value"].as_str().ok_or_else(|| anyhow!("no storage value"))?;
+        storage_values.push(hex::decode(pad_even(value.trim_start_matches("0x")))?);
     }
+    Ok(ProofResult { account_proof, storage_proofs, storage_values })
+}
+
+/// Decode a JSON array of `0x`-prefixed hex proof nodes into raw byte vectors.
+fn decode_proof_nodes(value: &serde_json::Value) -> Result<Vec<Vec<u8>>> {
+    value.as_array().ok_or_else(|| anyhow!("proof is not an array"))?
+        .iter()
+        .map(|n| {
+            let s = n.as_str().ok_or_else(|| anyhow!("proof node is not a string"))?;
+            Ok(hex::decode(s.trim_start_matches("0x"))?)
+        })
+        .collect()
+}
+
+/// Storage key for `balances[holder]` at mapping slot `slot`:
+/// keccak256(pad32(holder) ++ pad32(slot)).
+fn balance_slot_key(holder: &str, slot: u64) -> Result<[u8; 32]> {
+    let holder20 = addr_to_20(holder)?;
+    let mut buf = [0u8; 64];
+    buf[12..32].copy_from_slice(&holder20);
+    buf[56..64].copy_from_slice(&slot.to_be_bytes());
+    Ok(Keccak256::digest(buf).into())
+}
+
+/// Left-pad a big-endian byte string to 32 bytes.
+fn left_pad_be(bytes: &[u8]) -> [u8; 32] {
     let mut out = [0u8; 32];
-    out.copy_from_slice(&bytes);
-    Ok(out)
+    out[32 - bytes.len()..].copy_from_slice(bytes);
+    out
+}
+
+/// Ensure a hex string has an even number of digits (RPC trims leading zeros).
+fn pad_even(s: &str) -> String {
+    if s.len() % 2 == 1 {
+        format!("0{s}")
+    } else {
+        s.to_string()
+    }
 }
 
 fn get_code_at(client: &Client, rpc: &str, addr: &str, block: u64) -> Result<Vec<u8>> {
@@ -202,11 +364,24 @@ fn get_code_at(client: &Client, rpc: &str, addr: &str, block: u64) -> Result<Vec
     Ok(bytes)
 }
 
-fn left_pad_32(s20: &str) -> Result<String> {
-    if !s20.starts_with("0x") || s20.len() != 42 {
-        bail!("address must be 0x + 40 hex chars");
+fn hex32(s: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(s.trim_start_matches("0x"))?;
+    if bytes.len() != 32 {
+        bail!("expected 32 bytes, got {}", bytes.len());
     }
-    Ok(format!("{:0>64}", s20.trim_start_matches("0x").to_lowercase()))
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
+
+fn parse_fork_version(s: &str) -> Result<[u8; 4]> {
+    let bytes = hex::decode(s.trim_start_matches("0x"))?;
+    if bytes.len() != 4 {
+        bail!("fork version must be 4 bytes");
+    }
+    let mut out = [0u8; 4];
+    out.copy_from_slice(&bytes);
+    Ok(out)
 }
 
 fn addr_to_20(a: &str) -> Result<[u8; 20]> {
@@ -232,9 +407,17 @@ fn read_calldata(path: &PathBuf) -> Result<Vec<u8>> {
 }
 
 // Must mirror the guest's commitment exactly.
-fn commit_all_host(pre_post: &[u8; 64], calldata: &[u8], target_code: &[u8], asset_code: &[u8]) -> [u8; 32] {
+fn commit_all_host(
+    pre_post: &[u8; 64],
+    calldata: &[u8],
+    target_code: &[u8],
+    asset_code: &[u8],
+    holders: &[([u8; 20], [u8; 32])],
+    storage: &[([u8; 32], [u8; 32])],
+) -> [u8; 32] {
     // sha256( "BBP" || len(pre_post) || pre_post || len(calldata) || calldata
-    //                 || len(target_code)|| target_code || len(asset_code)|| asset_code )
+    //                 || len(target_code)|| target_code || len(asset_code)|| asset_code
+    //                 || len(holders) || holders || len(storage) || storage )
     let mut hasher = Sha256::new();
     hasher.update(b"BBP");
     hasher.update((pre_post.len() as u32).to_be_bytes());
@@ -245,5 +428,15 @@ fn commit_all_host(pre_post: &[u8; 64], calldata: &[u8], target_code: &[u8], ass
     hasher.update(target_code);
     hasher.update((asset_code.len() as u32).to_be_bytes());
     hasher.update(asset_code);
+    hasher.update((holders.len() as u32).to_be_bytes());
+    for (addr, value) in holders {
+        hasher.update(addr);
+        hasher.update(value);
+    }
+    hasher.update((storage.len() as u32).to_be_bytes());
+    for (slot, value) in storage {
+        hasher.update(slot);
+        hasher.update(value);
+    }
     hasher.finalize().into()
 }
\ No newline at end of file
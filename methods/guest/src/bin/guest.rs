@@ -3,11 +3,24 @@
 
 extern crate alloc;
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use methods::u256::U256;
 use methods::{PublicInputs, PublicOutputs};
 use risc0_zkvm::guest::env;
 use risc0_zkvm::sha;
 
+#[path = "../keccak.rs"]
+mod keccak;
+#[path = "../rlp.rs"]
+mod rlp;
+#[path = "../mpt.rs"]
+mod mpt;
+#[path = "../evm.rs"]
+mod evm;
+
+use keccak::keccak256;
+
 risc0_zkvm::guest::entry!(main);
 
 fn main() {
@@ -23,9 +36,15 @@ fn main() {
     let target_code: Vec<u8> = env::read();
     //  (4) asset bytecode
     let asset_code: Vec<u8> = env::read();
+    //  (5) Merkle-Patricia witness proving the above against the state roots
+    let proofs: StateWitness = env::read();
+    //  (6) extra ERC-20 holder balances the exploit touches (addr -> u256)
+    let holders: Vec<([u8; 20], [u8; 32])> = env::read();
+    //  (7) pre-state storage witness for the target (slot -> value)
+    let storage: Vec<([u8; 32], [u8; 32])> = env::read();
 
     // ---- Recompute the overall commitment over length-tagged blobs ----
-    let commitment = commit_all(&pre_post, &calldata, &target_code, &asset_code);
+    let commitment = commit_all(&pre_post, &calldata, &target_code, &asset_code, &holders, &storage);
     assert_eq!(commitment, pubin.commitment, "commitment mismatch");
 
     // ---- Sanity checks on calldata & code digests (bind PoC to actual code) ----
@@ -33,26 +52,96 @@ fn main() {
     assert!(calldata.len() >= 4, "calldata too short");
     assert_eq!(&calldata[0..4], &pubin.selector, "selector mismatch");
 
-    // SHA-256 of code blobs must match public digests
-    let t_sha = sha::sha256(&target_code);
-    let a_sha = sha::sha256(&asset_code);
-    assert_eq!(t_sha.as_bytes(), &pubin.target_code_sha256, "target code digest mismatch");
-    assert_eq!(a_sha.as_bytes(), &pubin.asset_code_sha256, "asset code digest mismatch");
+    // keccak256 of the code blobs must match the public code hashes, and those
+    // code hashes must themselves be committed by the state trie (below). This
+    // replaces the old SHA-256 bind, which never matched Ethereum's `codeHash`.
+    assert_eq!(keccak256(&target_code), pubin.target_code_hash, "target code digest mismatch");
+    assert_eq!(keccak256(&asset_code), pubin.asset_code_hash, "asset code digest mismatch");
+
+    // ---- Trustless state verification ----
+    // Account leaf = RLP([nonce, balance, storageHash, codeHash]). We pin the
+    // target's codeHash against the committed `target_code_hash`, and the
+    // asset's storageHash is the root we traverse for the ERC-20 balances.
+    let (_, _, target_storage_root, target_code_hash) =
+        verify_account(&pubin.state_root, &pubin.target, &proofs.target_account_pre);
+    assert_eq!(target_code_hash, pubin.target_code_hash, "target codeHash mismatch vs state");
+
+    // Asset storageHash at the pre and post blocks.
+    let (_, _, asset_storage_pre, asset_code_hash) =
+        verify_account(&pubin.state_root, &pubin.asset, &proofs.asset_account_pre);
+    assert_eq!(asset_code_hash, pubin.asset_code_hash, "asset codeHash mismatch vs state");
+    let (_, _, asset_storage_post, _) =
+        verify_account(&pubin.post_state_root, &pubin.asset, &proofs.asset_account_post);
+
+    // Balance slot for balances[target] and its proven values at pre/post.
+    let slot_key = balance_slot_key(&pubin.target, pubin.balances_slot);
+    let pre = verify_balance(&asset_storage_pre, &slot_key, &proofs.balance_storage_pre);
+    let post = verify_balance(&asset_storage_post, &slot_key, &proofs.balance_storage_post);
+
+    // The proven balances must equal the committed witness (keeps the journal
+    // and the commitment consistent with what the trie actually stores).
+    assert_eq!(&pre, &pre_post[0..32], "pre balance differs from proof");
+    assert_eq!(&post, &pre_post[32..64], "post balance differs from proof");
+
+    // ---- Prove the EVM seed state against the trie ----
+    // The holder balances and target storage the EVM starts from must be
+    // Merkle-proven too, otherwise a prover could fabricate them to drive any
+    // loss. Holder balances are `balances[holder]` in the asset's pre storage
+    // trie; target storage slots live in the target's own storage trie.
+    assert_eq!(holders.len(), proofs.holder_storage.len(), "holder proof count mismatch");
+    assert_eq!(storage.len(), proofs.target_storage.len(), "target storage proof count mismatch");
+    for ((addr, value), proof) in holders.iter().zip(&proofs.holder_storage) {
+        let key = balance_slot_key(addr, pubin.balances_slot);
+        let proven = verify_balance(&asset_storage_pre, &key, proof);
+        assert_eq!(&proven, value, "holder balance differs from proof");
+    }
+    for ((slot, value), proof) in storage.iter().zip(&proofs.target_storage) {
+        let proven = verify_balance(&target_storage_root, slot, proof);
+        assert_eq!(&proven, value, "target storage differs from proof");
+    }
+
+    // ---- Actually execute the PoC and measure the realized loss ----
+    // Seed the in-guest EVM with the MPT-proven pre balance (so execution
+    // starts from the real chain value) plus the now-proven holder balances and
+    // target storage, then run the calldata. The committed loss is the
+    // `balanceOf(target)` delta the exploit *produces*.
+    let mut token_balances: BTreeMap<[u8; 20], U256> = BTreeMap::new();
+    token_balances.insert(pubin.target, U256::from_be_bytes(&pre));
+    for (addr, value) in &holders {
+        token_balances.insert(*addr, U256::from_be_bytes(value));
+    }
+    let mut target_storage: BTreeMap<U256, U256> = BTreeMap::new();
+    for (slot, value) in &storage {
+        target_storage.insert(U256::from_be_bytes(slot), U256::from_be_bytes(value));
+    }
+
+    let ctx = evm::ExecContext {
+        target: pubin.target,
+        caller: [0u8; 20],
+        asset: pubin.asset,
+        token_balances,
+        storage: target_storage,
+    };
+    let (_result, loss) = evm::execute(&target_code, &calldata, ctx, &evm::ExecConfig::default());
 
-    // ---- Parse balances & check threshold ----
-    let mut pre = [0u8; 32];
-    let mut post = [0u8; 32];
-    pre.copy_from_slice(&pre_post[0..32]);
-    post.copy_from_slice(&pre_post[32..64]);
+    // `loss` is NOT bounded by the pre/post balance delta: a PoC proves a
+    // *hypothetical* exploit against MPT-proven seed state, and for a latent
+    // bug the drain has not happened on-chain yet (pre == post). Clamping loss
+    // to the realized delta would force every unexploited PoC to commit zero,
+    // collapsing "prove this would drain X" into "attest a drain that already
+    // occurred". The post-state proof remains load-bearing for its own
+    // purpose — pinning the committed `post` witness to a real, trie-committed
+    // value — it just isn't used to cap what the EVM execution can prove.
 
-    let loss = sub_u256_be_saturating(&pre, &post);
-    let ge = ge_u256_vs_u128(&loss, pubin.threshold);
+    // ---- Check threshold ----
+    let ge = loss >= U256::from_be_bytes(&pubin.threshold);
+    let loss_bytes = loss.to_be_bytes();
 
     // ---- Commit outputs ----
     let mut hi = [0u8; 16];
     let mut lo = [0u8; 16];
-    hi.copy_from_slice(&loss[0..16]);
-    lo.copy_from_slice(&loss[16..32]);
+    hi.copy_from_slice(&loss_bytes[0..16]);
+    lo.copy_from_slice(&loss_bytes[16..32]);
 
     let out = PublicOutputs {
         threshold: pubin.threshold,
@@ -62,13 +151,101 @@ fn main() {
         selector: pubin.selector,
         asset: pubin.asset,
         target: pubin.target,
+        state_root: pubin.state_root,
+        post_state_root: pubin.post_state_root,
+        target_code_hash: pubin.target_code_hash,
+        asset_code_hash: pubin.asset_code_hash,
     };
     env::commit(&out);
 }
 
+/// Merkle-Patricia witness: account proofs against the state roots plus storage
+/// proofs for `balances[target]` at the pre and post blocks. Each entry is the
+/// ordered list of RLP-encoded trie nodes returned by `eth_getProof`.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct StateWitness {
+    target_account_pre: Vec<Vec<u8>>,
+    asset_account_pre: Vec<Vec<u8>>,
+    asset_account_post: Vec<Vec<u8>>,
+    balance_storage_pre: Vec<Vec<u8>>,
+    balance_storage_post: Vec<Vec<u8>>,
+    /// Storage proof per entry in `holders`, against the asset's pre storageHash.
+    holder_storage: Vec<Vec<Vec<u8>>>,
+    /// Storage proof per entry in `storage`, against the target's storageHash.
+    target_storage: Vec<Vec<Vec<u8>>>,
+}
+
+/// Verify an account against `state_root` and decode its leaf into
+/// `(nonce, balance, storageHash, codeHash)`.
+fn verify_account(
+    state_root: &[u8; 32],
+    address: &[u8; 20],
+    proof: &[Vec<u8>],
+) -> ([u8; 32], [u8; 32], [u8; 32], [u8; 32]) {
+    let path = keccak256(address);
+    let leaf = mpt::verify_proof(state_root, &path, proof);
+    let (item, _) = rlp::decode(&leaf);
+    let fields = item.list();
+    assert_eq!(fields.len(), 4, "account leaf must have 4 fields");
+    (
+        left_pad_32(fields[0].bytes()),
+        left_pad_32(fields[1].bytes()),
+        expect_hash(fields[2].bytes()),
+        expect_hash(fields[3].bytes()),
+    )
+}
+
+/// Verify `balances[target]` against the asset's `storageHash` and return the
+/// 32-byte big-endian balance. Storage leaves hold the RLP of the value with
+/// leading zero bytes trimmed, so we left-pad back to 32 bytes.
+fn verify_balance(storage_root: &[u8; 32], slot_key: &[u8; 32], proof: &[Vec<u8>]) -> [u8; 32] {
+    let path = keccak256(slot_key);
+    let leaf = mpt::verify_proof(storage_root, &path, proof);
+    // An exclusion proof (empty leaf) means the slot was removed from the trie,
+    // i.e. its value is zero — the maximal-drain case where post balance is 0.
+    if leaf.is_empty() {
+        return [0u8; 32];
+    }
+    let (item, _) = rlp::decode(&leaf);
+    left_pad_32(item.bytes())
+}
+
+/// Storage key for `balances[holder]` where `balances` lives at `slot`:
+/// keccak256(pad32(holder) ++ pad32(slot)).
+fn balance_slot_key(holder: &[u8; 20], slot: u64) -> [u8; 32] {
+    let mut buf = [0u8; 64];
+    buf[12..32].copy_from_slice(holder);
+    buf[56..64].copy_from_slice(&slot.to_be_bytes());
+    keccak256(&buf)
+}
+
+/// Left-pad a big-endian byte string into a 32-byte array.
+fn left_pad_32(bytes: &[u8]) -> [u8; 32] {
+    assert!(bytes.len() <= 32, "value wider than 32 bytes");
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(bytes);
+    out
+}
+
+/// Require a field to be exactly a 32-byte hash.
+fn expect_hash(bytes: &[u8]) -> [u8; 32] {
+    assert_eq!(bytes.len(), 32, "expected 32-byte hash");
+    let mut out = [0u8; 32];
+    out.copy_from_slice(bytes);
+    out
+}
+
 // Compute: sha256( "BBP" || len(pre_post) || pre_post || len(calldata) || calldata
-//                     || len(target_code) || target_code || len(asset_code) || asset_code )
-fn commit_all(pre_post: &[u8; 64], calldata: &[u8], target_code: &[u8], asset_code: &[u8]) -> [u8; 32] {
+//                     || len(target_code) || target_code || len(asset_code) || asset_code
+//                     || len(holders) || holders || len(storage) || storage )
+fn commit_all(
+    pre_post: &[u8; 64],
+    calldata: &[u8],
+    target_code: &[u8],
+    asset_code: &[u8],
+    holders: &[([u8; 20], [u8; 32])],
+    storage: &[([u8; 32], [u8; 32])],
+) -> [u8; 32] {
     let mut st = sha::Impl::new();
     st.update(b"BBP");
     write_len(&mut st, pre_post.len() as u32);
@@ -79,6 +256,16 @@ fn commit_all(pre_post: &[u8; 64], calldata: &[u8], target_code: &[u8], asset_co
     st.update(target_code);
     write_len(&mut st, asset_code.len() as u32);
     st.update(asset_code);
+    write_len(&mut st, holders.len() as u32);
+    for (addr, value) in holders {
+        st.update(addr);
+        st.update(value);
+    }
+    write_len(&mut st, storage.len() as u32);
+    for (slot, value) in storage {
+        st.update(slot);
+        st.update(value);
+    }
     *st.finalize().as_bytes()
 }
 
@@ -86,33 +273,3 @@ fn write_len(st: &mut sha::Impl, n: u32) {
     let be = n.to_be_bytes();
     st.update(&be);
 }
-
-// ---------- helpers (big-endian arithmetic) ----------
-
-fn sub_u256_be_saturating(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-    let mut out = [0u8; 32];
-    let mut borrow: u16 = 0;
-    for i in (0..32).rev() {
-        let av = a[i] as u16;
-        let bv = b[i] as u16;
-        let mut diff = av.wrapping_sub(bv + borrow);
-        if av < bv + borrow {
-            borrow = 1;
-            diff = diff.wrapping_add(1 << 8);
-        } else {
-            borrow = 0;
-        }
-        out[i] = (diff & 0xff) as u8;
-    }
-    if borrow != 0 { [0u8; 32] } else { out }
-}
-
-fn ge_u256_vs_u128(a: &[u8; 32], thr: u128) -> bool {
-    for b in &a[0..16] {
-        if *b != 0 { return true; }
-    }
-    let mut lo_bytes = [0u8; 16];
-    lo_bytes.copy_from_slice(&a[16..32]);
-    let lo = u128::from_be_bytes(lo_bytes);
-    lo >= thr
-}
\ No newline at end of file
@@ -0,0 +1,104 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use methods::light_client::{
+    LightClientInputs, LightClientOutputs, LightClientUpdate, EXECUTION_PAYLOAD_INDEX,
+    FINALIZED_ROOT_INDEX, SYNC_COMMITTEE_SIZE,
+};
+use risc0_zkvm::guest::env;
+
+#[path = "../ssz.rs"]
+mod ssz;
+#[path = "../bls.rs"]
+mod bls;
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let pubin: LightClientInputs = env::read();
+    let update: LightClientUpdate = env::read();
+
+    let committee = &pubin.sync_committee;
+    assert_eq!(committee.pubkeys.len(), SYNC_COMMITTEE_SIZE, "sync committee size");
+
+    // (1) Signing root over the attested header in the sync-committee domain.
+    let domain = ssz::sync_committee_domain(&update.fork_version, &update.genesis_validators_root);
+    let object_root = ssz::header_root(&update.attested_header);
+    let signing_root = ssz::signing_root(&object_root, &domain);
+
+    // (2) Select participating pubkeys, require >= 2/3 participation, verify BLS.
+    let participants = participating_pubkeys(
+        &committee.pubkeys,
+        &update.sync_aggregate.sync_committee_bits,
+    );
+    assert!(
+        participants.len() * 3 >= SYNC_COMMITTEE_SIZE * 2,
+        "insufficient sync-committee participation: {}/{}",
+        participants.len(),
+        SYNC_COMMITTEE_SIZE
+    );
+    assert!(
+        bls::fast_aggregate_verify(
+            &participants,
+            &signing_root,
+            &update.sync_aggregate.sync_committee_signature,
+        ),
+        "sync-committee signature invalid"
+    );
+
+    // (3) Prove the finalized header against the attested header's state root.
+    let finalized_root = ssz::header_root(&update.finalized_header);
+    assert!(
+        ssz::verify_branch(
+            &finalized_root,
+            &update.finality_branch,
+            FINALIZED_ROOT_INDEX,
+            &update.attested_header.state_root,
+        ),
+        "finality branch invalid"
+    );
+
+    // (4) Prove the execution payload header against the finalized block body,
+    // then read `state_root` out of the header whose root we just authenticated.
+    // Recomputing the header root from its fields is what binds `state_root`;
+    // feeding `state_root` in directly (as before) proved nothing.
+    let payload_root = ssz::execution_payload_header_root(&update.execution_payload_header);
+    assert!(
+        ssz::verify_branch(
+            &payload_root,
+            &update.execution_branch,
+            EXECUTION_PAYLOAD_INDEX,
+            &update.finalized_header.body_root,
+        ),
+        "execution branch invalid"
+    );
+
+    // Commit the committee that actually verified the signature (as a digest)
+    // alongside the signature slot that selects its period, so a verifier can
+    // confirm we used the canonical committee rather than an attacker's.
+    let out = LightClientOutputs {
+        state_root: update.execution_payload_header.state_root,
+        slot: update.finalized_header.slot,
+        block_number: update.execution_payload_header.block_number,
+        committee_hash: ssz::sync_committee_hash(committee),
+        signature_slot: update.signature_slot,
+    };
+    env::commit(&out);
+}
+
+/// Collect the pubkeys whose participation bit is set in the little-endian
+/// bitfield.
+fn participating_pubkeys(
+    pubkeys: &[[u8; 48]],
+    bits: &[u8],
+) -> alloc::vec::Vec<[u8; 48]> {
+    let mut out = alloc::vec::Vec::new();
+    for (i, pk) in pubkeys.iter().enumerate() {
+        if bits[i / 8] & (1 << (i % 8)) != 0 {
+            out.push(*pk);
+        }
+    }
+    out
+}
@@ -0,0 +1,79 @@
+//! BLS12-381 `fast_aggregate_verify` for sync-committee signatures.
+//!
+//! On the host and any std target this dispatches to `blst`; inside the zkVM
+//! (`target_os = "zkvm"`), where `blst`'s assembly is unavailable, it falls
+//! back to the pure-Rust `bls12_381` implementation. Both follow the
+//! `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite the beacon chain
+//! uses, so the verification result is identical.
+
+use alloc::vec::Vec;
+
+/// Verify that `signature` is the aggregate of signatures over `message` by the
+/// keys in `pubkeys` (eth2 `fast_aggregate_verify`). Returns `false` on any
+/// malformed point rather than panicking.
+pub fn fast_aggregate_verify(pubkeys: &[[u8; 48]], message: &[u8; 32], signature: &[u8; 96]) -> bool {
+    if pubkeys.is_empty() {
+        return false;
+    }
+    verify(pubkeys, message, signature)
+}
+
+#[cfg(not(target_os = "zkvm"))]
+fn verify(pubkeys: &[[u8; 48]], message: &[u8; 32], signature: &[u8; 96]) -> bool {
+    use blst::min_pk::{AggregatePublicKey, PublicKey, Signature};
+
+    const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    let keys: Vec<PublicKey> = match pubkeys
+        .iter()
+        .map(|pk| PublicKey::from_bytes(pk))
+        .collect::<Result<_, _>>()
+    {
+        Ok(k) => k,
+        Err(_) => return false,
+    };
+    let agg = match AggregatePublicKey::aggregate(&keys.iter().collect::<Vec<_>>(), false) {
+        Ok(a) => a.to_public_key(),
+        Err(_) => return false,
+    };
+    let sig = match Signature::from_bytes(signature) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    sig.verify(true, message, DST, &[], &agg, true) == blst::BLST_ERROR::BLST_SUCCESS
+}
+
+#[cfg(target_os = "zkvm")]
+fn verify(pubkeys: &[[u8; 48]], message: &[u8; 32], signature: &[u8; 96]) -> bool {
+    use bls12_381::{
+        hash_to_curve::{ExpandMsgXmd, HashToCurve},
+        multi_miller_loop, G1Affine, G2Affine, G2Prepared, Gt,
+    };
+
+    const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    // Aggregate the G1 pubkeys.
+    let mut agg = G1Affine::identity().into();
+    for pk in pubkeys {
+        match Option::<G1Affine>::from(G1Affine::from_compressed(pk)) {
+            Some(p) => agg += p,
+            None => return false,
+        }
+    }
+    let agg = G1Affine::from(agg);
+
+    let sig = match Option::<G2Affine>::from(G2Affine::from_compressed(signature)) {
+        Some(s) => s,
+        None => return false,
+    };
+    let msg = <G2Affine as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(message, DST);
+    let msg = G2Affine::from(msg);
+
+    // e(-G1, signature) * e(pubkey, H(m)) == 1
+    let pairing = multi_miller_loop(&[
+        (&-G1Affine::generator(), &G2Prepared::from(sig)),
+        (&agg, &G2Prepared::from(msg)),
+    ])
+    .final_exponentiation();
+    pairing == Gt::identity()
+}
@@ -0,0 +1,488 @@
+//! A minimal no_std EVM interpreter, just enough to *execute* a PoC rather than
+//! assume it drains the whole balance.
+//!
+//! The interpreter runs the target's runtime bytecode over the provided
+//! calldata with a witnessed storage map. Calls into the asset address are
+//! resolved by a small ERC-20 shim (`balanceOf`/`transfer`/`transferFrom`)
+//! backed by the witnessed token state, so the committed loss is the real
+//! `balanceOf(target)` delta the exploit causes. Only deterministic opcodes are
+//! supported; gas metering is optional behind [`ExecConfig::meter_gas`].
+//!
+//! Supported opcode subset: arithmetic ADD/MUL/SUB/DIV/SDIV/MOD/SMOD/ADDMOD/
+//! MULMOD/EXP; comparison/bitwise LT/GT/SLT/SGT/EQ/ISZERO/AND/OR/XOR/NOT/BYTE/
+//! SHL/SHR; SHA3; the environment/call-data ops ADDRESS/CALLER/CALLVALUE/
+//! CALLDATALOAD/CALLDATASIZE/CALLDATACOPY/CODESIZE/CODECOPY/RETURNDATASIZE/
+//! RETURNDATACOPY/GAS; stack POP; memory MLOAD/MSTORE/MSTORE8; storage SLOAD/SSTORE; flow
+//! JUMP/JUMPI/PC/JUMPDEST/PUSH0..PUSH32/DUP/SWAP; and CALL/STATICCALL/RETURN/
+//! REVERT/STOP/INVALID. Any other opcode (notably block-context and
+//! non-deterministic ops) reverts, which yields zero loss — a PoC that needs
+//! them cannot be proven here.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::keccak::keccak256;
+use methods::u256::U256;
+
+/// Upper bound on linear-memory growth. Offsets come from attacker-chosen
+/// stack words, so an unbounded `Vec::resize` would let a PoC OOM the prover;
+/// cap expansion instead and fail deterministically.
+const MAX_MEMORY: usize = 1 << 24; // 16 MiB
+
+/// ERC-20 selectors the asset shim understands.
+const SEL_BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+const SEL_TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const SEL_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// Execution context: the acting contract, caller, and asset wiring.
+pub struct ExecContext {
+    pub target: [u8; 20],
+    pub caller: [u8; 20],
+    pub asset: [u8; 20],
+    /// Witnessed `balances` map, keyed by holder address.
+    pub token_balances: BTreeMap<[u8; 20], U256>,
+    /// Witnessed storage of the target contract, keyed by slot.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// Tunables for a run.
+pub struct ExecConfig {
+    pub meter_gas: bool,
+    pub gas_limit: u64,
+}
+
+impl Default for ExecConfig {
+    fn default() -> Self {
+        ExecConfig { meter_gas: false, gas_limit: u64::MAX }
+    }
+}
+
+/// Outcome of the top-level call.
+pub struct ExecResult {
+    pub reverted: bool,
+    pub return_data: Vec<u8>,
+}
+
+/// Run `code` with `calldata` and return the result together with the realized
+/// loss `balanceOf(target)_pre - balanceOf(target)_post`. A revert unwinds all
+/// token writes and yields zero loss.
+pub fn execute(
+    code: &[u8],
+    calldata: &[u8],
+    mut ctx: ExecContext,
+    cfg: &ExecConfig,
+) -> (ExecResult, U256) {
+    let pre = token_balance(&ctx, &ctx.target);
+
+    // Snapshot the token state so a revert can unwind it.
+    let snapshot = ctx.token_balances.clone();
+    let result = Interpreter::new(code, calldata, &mut ctx, cfg).run();
+
+    if result.reverted {
+        ctx.token_balances = snapshot;
+        return (result, U256::ZERO);
+    }
+
+    let post = token_balance(&ctx, &ctx.target);
+    let loss = pre.saturating_sub(&post);
+    (result, loss)
+}
+
+fn token_balance(ctx: &ExecContext, holder: &[u8; 20]) -> U256 {
+    ctx.token_balances.get(holder).copied().unwrap_or(U256::ZERO)
+}
+
+struct Interpreter<'a> {
+    code: &'a [u8],
+    calldata: &'a [u8],
+    ctx: &'a mut ExecContext,
+    cfg: &'a ExecConfig,
+    stack: Vec<U256>,
+    memory: Vec<u8>,
+    /// Return data of the most recent sub-call (RETURNDATASIZE/RETURNDATACOPY).
+    last_return: Vec<u8>,
+    pc: usize,
+    gas_used: u64,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(code: &'a [u8], calldata: &'a [u8], ctx: &'a mut ExecContext, cfg: &'a ExecConfig) -> Self {
+        Interpreter {
+            code,
+            calldata,
+            ctx,
+            cfg,
+            stack: Vec::with_capacity(1024),
+            memory: Vec::new(),
+            last_return: Vec::new(),
+            pc: 0,
+            gas_used: 0,
+        }
+    }
+
+    fn run(&mut self) -> ExecResult {
+        loop {
+            if self.pc >= self.code.len() {
+                return ok(Vec::new());
+            }
+            let op = self.code[self.pc];
+            self.pc += 1;
+            self.charge(op);
+
+            match op {
+                0x00 => return ok(Vec::new()), // STOP
+                0x01 => self.bin(|a, b| a.wrapping_add(&b)), // ADD
+                0x02 => self.bin(|a, b| a.wrapping_mul(&b)), // MUL
+                0x03 => self.bin(|a, b| a.wrapping_sub(&b)), // SUB
+                0x04 => self.bin(|a, b| if b.is_zero() { U256::ZERO } else { a.div(&b) }), // DIV
+                0x05 => self.bin(|a, b| a.sdiv(&b)), // SDIV
+                0x06 => self.bin(|a, b| if b.is_zero() { U256::ZERO } else { a.rem(&b) }), // MOD
+                0x07 => self.bin(|a, b| a.smod(&b)), // SMOD
+                0x08 => self.tri(|a, b, n| a.addmod(&b, &n)), // ADDMOD
+                0x09 => self.tri(|a, b, n| a.mulmod(&b, &n)), // MULMOD
+                0x0a => self.bin(|a, b| a.pow(&b)), // EXP
+                0x10 => self.bin(|a, b| U256::from_bool(a < b)), // LT
+                0x11 => self.bin(|a, b| U256::from_bool(a > b)), // GT
+                0x12 => self.bin(|a, b| U256::from_bool(a.slt(&b))), // SLT
+                0x13 => self.bin(|a, b| U256::from_bool(a.sgt(&b))), // SGT
+                0x14 => self.bin(|a, b| U256::from_bool(a == b)), // EQ
+                0x15 => self.un(|a| U256::from_bool(a.is_zero())), // ISZERO
+                0x16 => self.bin(|a, b| a.bitand(&b)), // AND
+                0x17 => self.bin(|a, b| a.bitor(&b)),  // OR
+                0x18 => self.bin(|a, b| a.bitxor(&b)), // XOR
+                0x19 => self.un(|a| a.not()),          // NOT
+                0x1a => self.bin(|a, b| a.byte(&b)),   // BYTE
+                0x1b => self.bin(|a, b| a.shl(&b)), // SHL (b << a)
+                0x1c => self.bin(|a, b| a.shr(&b)), // SHR (b >> a)
+                0x20 => self.keccak(),                 // SHA3
+                0x30 => self.push(U256::from_address(&self.ctx.target)), // ADDRESS
+                0x33 => self.push(U256::from_address(&self.ctx.caller)), // CALLER
+                0x34 => self.push(U256::ZERO),         // CALLVALUE (no ETH in PoC)
+                0x35 => self.calldataload(),           // CALLDATALOAD
+                0x36 => self.push(U256::from_u64(self.calldata.len() as u64)), // CALLDATASIZE
+                0x37 => self.calldatacopy(),           // CALLDATACOPY
+                0x38 => self.push(U256::from_u64(self.code.len() as u64)), // CODESIZE
+                0x39 => self.codecopy(),               // CODECOPY
+                0x3d => self.push(U256::from_u64(self.last_return.len() as u64)), // RETURNDATASIZE
+                0x3e => self.returndatacopy(),         // RETURNDATACOPY
+                0x50 => { self.pop(); }                // POP
+                0x51 => self.mload(),                  // MLOAD
+                0x52 => self.mstore(),                 // MSTORE
+                0x53 => self.mstore8(),                // MSTORE8
+                0x54 => self.sload(),                  // SLOAD
+                0x55 => self.sstore(),                 // SSTORE
+                0x56 => { if !self.jump() { return revert(Vec::new()); } } // JUMP
+                0x57 => { if !self.jumpi() { return revert(Vec::new()); } } // JUMPI
+                0x58 => self.push(U256::from_u64((self.pc - 1) as u64)), // PC
+                0x5a => self.push(U256::from_u64(self.cfg.gas_limit - self.gas_used)), // GAS
+                0x5b => {}                             // JUMPDEST
+                0x5f => self.push(U256::ZERO),         // PUSH0
+                0x60..=0x7f => self.push_n(op),        // PUSH1..PUSH32
+                0x80..=0x8f => self.dup(op),           // DUP1..DUP16
+                0x90..=0x9f => self.swap(op),          // SWAP1..SWAP16
+                0xf1 | 0xfa => self.call(op),          // CALL / STATICCALL
+                0xf3 => return ok(self.return_data()), // RETURN
+                0xfd => return revert(self.return_data()), // REVERT
+                0xfe => return revert(Vec::new()),     // INVALID
+                _ => return revert(Vec::new()),        // unsupported/non-deterministic
+            }
+        }
+    }
+
+    // ---- stack helpers ----
+
+    fn push(&mut self, v: U256) {
+        assert!(self.stack.len() < 1024, "stack overflow");
+        self.stack.push(v);
+    }
+
+    fn pop(&mut self) -> U256 {
+        self.stack.pop().expect("stack underflow")
+    }
+
+    fn un(&mut self, f: impl Fn(U256) -> U256) {
+        let a = self.pop();
+        self.push(f(a));
+    }
+
+    fn bin(&mut self, f: impl Fn(U256, U256) -> U256) {
+        let a = self.pop();
+        let b = self.pop();
+        self.push(f(a, b));
+    }
+
+    fn tri(&mut self, f: impl Fn(U256, U256, U256) -> U256) {
+        let a = self.pop();
+        let b = self.pop();
+        let c = self.pop();
+        self.push(f(a, b, c));
+    }
+
+    fn push_n(&mut self, op: u8) {
+        let n = (op - 0x5f) as usize;
+        let mut bytes = [0u8; 32];
+        for i in 0..n {
+            let b = self.code.get(self.pc + i).copied().unwrap_or(0);
+            bytes[32 - n + i] = b;
+        }
+        self.pc += n;
+        self.push(U256::from_be_bytes(&bytes));
+    }
+
+    fn dup(&mut self, op: u8) {
+        let n = (op - 0x7f) as usize;
+        let v = self.stack[self.stack.len() - n];
+        self.push(v);
+    }
+
+    fn swap(&mut self, op: u8) {
+        let n = (op - 0x8f) as usize;
+        let len = self.stack.len();
+        self.stack.swap(len - 1, len - 1 - n);
+    }
+
+    // ---- memory ----
+
+    fn ensure(&mut self, end: usize) {
+        assert!(end <= MAX_MEMORY, "memory expansion exceeds cap");
+        if self.memory.len() < end {
+            self.memory.resize(end, 0);
+        }
+    }
+
+    fn mload(&mut self) {
+        let off = self.pop().as_usize();
+        self.ensure(off + 32);
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&self.memory[off..off + 32]);
+        self.push(U256::from_be_bytes(&bytes));
+    }
+
+    fn mstore(&mut self) {
+        let off = self.pop().as_usize();
+        let v = self.pop();
+        self.ensure(off + 32);
+        self.memory[off..off + 32].copy_from_slice(&v.to_be_bytes());
+    }
+
+    fn mstore8(&mut self) {
+        let off = self.pop().as_usize();
+        let v = self.pop();
+        self.ensure(off + 1);
+        self.memory[off] = v.to_be_bytes()[31];
+    }
+
+    fn return_data(&mut self) -> Vec<u8> {
+        let off = self.pop().as_usize();
+        let len = self.pop().as_usize();
+        self.ensure(off + len);
+        self.memory[off..off + len].to_vec()
+    }
+
+    // ---- calldata ----
+
+    fn calldataload(&mut self) {
+        let off = self.pop().as_usize();
+        let mut bytes = [0u8; 32];
+        for i in 0..32 {
+            bytes[i] = self.calldata.get(off + i).copied().unwrap_or(0);
+        }
+        self.push(U256::from_be_bytes(&bytes));
+    }
+
+    fn calldatacopy(&mut self) {
+        let dst = self.pop().as_usize();
+        let src = self.pop().as_usize();
+        let len = self.pop().as_usize();
+        self.ensure(dst + len);
+        for i in 0..len {
+            self.memory[dst + i] = self.calldata.get(src + i).copied().unwrap_or(0);
+        }
+    }
+
+    fn codecopy(&mut self) {
+        let dst = self.pop().as_usize();
+        let src = self.pop().as_usize();
+        let len = self.pop().as_usize();
+        self.ensure(dst + len);
+        for i in 0..len {
+            self.memory[dst + i] = self.code.get(src + i).copied().unwrap_or(0);
+        }
+    }
+
+    fn returndatacopy(&mut self) {
+        let dst = self.pop().as_usize();
+        let src = self.pop().as_usize();
+        let len = self.pop().as_usize();
+        self.ensure(dst + len);
+        for i in 0..len {
+            self.memory[dst + i] = self.last_return.get(src + i).copied().unwrap_or(0);
+        }
+    }
+
+    // ---- storage ----
+
+    fn sload(&mut self) {
+        let key = self.pop();
+        let v = self.ctx.storage.get(&key).copied().unwrap_or(U256::ZERO);
+        self.push(v);
+    }
+
+    fn sstore(&mut self) {
+        let key = self.pop();
+        let v = self.pop();
+        self.ctx.storage.insert(key, v);
+    }
+
+    // ---- control flow ----
+
+    fn jump(&mut self) -> bool {
+        let dst = self.pop().as_usize();
+        if self.code.get(dst) == Some(&0x5b) {
+            self.pc = dst;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn jumpi(&mut self) -> bool {
+        let dst = self.pop().as_usize();
+        let cond = self.pop();
+        if cond.is_zero() {
+            return true;
+        }
+        if self.code.get(dst) == Some(&0x5b) {
+            self.pc = dst;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn keccak(&mut self) {
+        let off = self.pop().as_usize();
+        let len = self.pop().as_usize();
+        self.ensure(off + len);
+        let hash = keccak256(&self.memory[off..off + len]);
+        self.push(U256::from_be_bytes(&hash));
+    }
+
+    // ---- external calls (ERC-20 shim) ----
+
+    fn call(&mut self, op: u8) {
+        // Stack layout: gas, addr, [value], argsOff, argsLen, retOff, retLen.
+        let _gas = self.pop();
+        let addr = self.pop().as_address();
+        if op == 0xf1 {
+            let _value = self.pop();
+        }
+        let args_off = self.pop().as_usize();
+        let args_len = self.pop().as_usize();
+        let ret_off = self.pop().as_usize();
+        let ret_len = self.pop().as_usize();
+
+        self.ensure(args_off + args_len);
+        let args = self.memory[args_off..args_off + args_len].to_vec();
+
+        // STATICCALL (0xfa) forbids state mutation; only views may run.
+        let read_only = op == 0xfa;
+        let (success, ret) = if addr == self.ctx.asset {
+            self.asset_call(&args, read_only)
+        } else {
+            // Unknown callee: treat as a no-op success returning empty data.
+            (true, Vec::new())
+        };
+
+        self.last_return = ret.clone();
+        self.ensure(ret_off + ret_len);
+        for i in 0..ret_len.min(ret.len()) {
+            self.memory[ret_off + i] = ret[i];
+        }
+        self.push(U256::from_bool(success));
+    }
+
+    /// Resolve a call into the asset against the witnessed ERC-20 state.
+    /// `read_only` (STATICCALL) rejects the mutating transfer selectors.
+    fn asset_call(&mut self, args: &[u8], read_only: bool) -> (bool, Vec<u8>) {
+        if args.len() < 4 {
+            return (false, Vec::new());
+        }
+        let mut sel = [0u8; 4];
+        sel.copy_from_slice(&args[0..4]);
+        match sel {
+            SEL_BALANCE_OF => {
+                let holder = word_address(args, 0);
+                let bal = self.ctx.token_balances.get(&holder).copied().unwrap_or(U256::ZERO);
+                (true, bal.to_be_bytes().to_vec())
+            }
+            SEL_TRANSFER if !read_only => {
+                let to = word_address(args, 0);
+                let amount = word_u256(args, 1);
+                (self.token_transfer(self.ctx.target, to, amount), ok_word())
+            }
+            SEL_TRANSFER_FROM if !read_only => {
+                let from = word_address(args, 0);
+                let to = word_address(args, 1);
+                let amount = word_u256(args, 2);
+                (self.token_transfer(from, to, amount), ok_word())
+            }
+            _ => (false, Vec::new()),
+        }
+    }
+
+    fn token_transfer(&mut self, from: [u8; 20], to: [u8; 20], amount: U256) -> bool {
+        let from_bal = self.ctx.token_balances.get(&from).copied().unwrap_or(U256::ZERO);
+        if from_bal < amount {
+            return false;
+        }
+        self.ctx.token_balances.insert(from, from_bal.wrapping_sub(&amount));
+        let to_bal = self.ctx.token_balances.get(&to).copied().unwrap_or(U256::ZERO);
+        self.ctx.token_balances.insert(to, to_bal.wrapping_add(&amount));
+        true
+    }
+
+    fn charge(&mut self, _op: u8) {
+        if self.cfg.meter_gas {
+            // Flat per-opcode charge keeps metering deterministic; refine per
+            // opcode if a request ever needs exact gas accounting.
+            self.gas_used += 1;
+            assert!(self.gas_used <= self.cfg.gas_limit, "out of gas");
+        }
+    }
+}
+
+fn ok(return_data: Vec<u8>) -> ExecResult {
+    ExecResult { reverted: false, return_data }
+}
+
+fn revert(return_data: Vec<u8>) -> ExecResult {
+    ExecResult { reverted: true, return_data }
+}
+
+/// A 32-byte ABI word holding a 1 (ERC-20 success return value).
+fn ok_word() -> Vec<u8> {
+    let mut w = [0u8; 32];
+    w[31] = 1;
+    w.to_vec()
+}
+
+/// Decode the `i`-th ABI word (after the 4-byte selector) as an address.
+fn word_address(args: &[u8], i: usize) -> [u8; 20] {
+    let start = 4 + i * 32;
+    let mut out = [0u8; 20];
+    if args.len() >= start + 32 {
+        out.copy_from_slice(&args[start + 12..start + 32]);
+    }
+    out
+}
+
+/// Decode the `i`-th ABI word (after the 4-byte selector) as a `U256`.
+fn word_u256(args: &[u8], i: usize) -> U256 {
+    let start = 4 + i * 32;
+    let mut bytes = [0u8; 32];
+    if args.len() >= start + 32 {
+        bytes.copy_from_slice(&args[start..start + 32]);
+    }
+    U256::from_be_bytes(&bytes)
+}
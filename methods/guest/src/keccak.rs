@@ -0,0 +1,15 @@
+//! keccak256 over the risc0 accelerator.
+//!
+//! The zkVM patches `tiny-keccak` to a precompile, so this is cheap inside the
+//! guest and behaves like plain keccak everywhere else.
+
+use tiny_keccak::{Hasher, Keccak};
+
+/// keccak256 of `data`.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(data);
+    let mut out = [0u8; 32];
+    hasher.finalize(&mut out);
+    out
+}
@@ -0,0 +1,109 @@
+//! Merkle-Patricia trie proof verification.
+//!
+//! Given an ordered list of RLP-encoded proof nodes, a `root` hash and a key,
+//! [`verify_proof`] walks the trie the way `eth_getProof` intends: each node's
+//! keccak256 must equal the hash the parent referenced (or `root` at the top),
+//! and the key's nibble path selects branch children / matches extension and
+//! leaf prefixes. The terminal leaf's value is returned.
+
+use alloc::vec::Vec;
+
+use crate::keccak::keccak256;
+use crate::rlp::decode;
+
+/// Verify an MPT proof for `key` under `root`. Returns the value stored at the
+/// leaf for an inclusion proof, or an empty `Vec` for an exclusion proof (the
+/// key is provably absent, e.g. a fully-drained storage slot that was removed
+/// from the trie). Panics if the proof does not authenticate either way.
+pub fn verify_proof(root: &[u8; 32], key: &[u8], proof: &[Vec<u8>]) -> Vec<u8> {
+    let nibbles = to_nibbles(key);
+    let mut expected = *root;
+    let mut depth = 0usize; // nibbles consumed so far
+
+    for (i, node_rlp) in proof.iter().enumerate() {
+        // Each referenced node is bound by its keccak256 hash.
+        assert_eq!(keccak256(node_rlp), expected, "node hash mismatch at depth {}", i);
+        let (node, _) = decode(node_rlp);
+        let items = node.list();
+
+        match items.len() {
+            // Branch node: 16 child slots + a value slot.
+            17 => {
+                if depth == nibbles.len() {
+                    // Path exhausted at a branch: the value lives in slot 16
+                    // (empty when the key is absent at this node).
+                    return items[16].bytes().to_vec();
+                }
+                let nib = nibbles[depth] as usize;
+                let child = items[nib].bytes();
+                if child.is_empty() {
+                    // Empty child on our path: the key is absent (exclusion).
+                    return Vec::new();
+                }
+                expected = expect_hash(child);
+                depth += 1;
+            }
+            // Extension or leaf node: [compact-encoded path, value-or-child].
+            2 => {
+                let (prefix_nibbles, is_leaf) = decode_compact(items[0].bytes());
+                if !nibbles[depth..].starts_with(&prefix_nibbles) {
+                    // Path diverges here: this node belongs to a different key,
+                    // so ours is absent (exclusion).
+                    return Vec::new();
+                }
+                depth += prefix_nibbles.len();
+                if is_leaf {
+                    // A matching prefix shorter than the remaining path means
+                    // the leaf keys a longer, different slot — ours is absent.
+                    if depth != nibbles.len() {
+                        return Vec::new();
+                    }
+                    return items[1].bytes().to_vec();
+                }
+                expected = expect_hash(items[1].bytes());
+            }
+            n => panic!("unexpected trie node with {} items", n),
+        }
+    }
+
+    panic!("proof exhausted before reaching a terminal node");
+}
+
+/// Bind a non-empty child reference as a 32-byte hash. Inline (embedded) nodes
+/// are uncommon for the account/storage tries we target, so we require the
+/// 32-byte hash form; emptiness is handled by the caller as an exclusion.
+fn expect_hash(bytes: &[u8]) -> [u8; 32] {
+    assert_eq!(bytes.len(), 32, "expected 32-byte child hash, found {} bytes", bytes.len());
+    let mut out = [0u8; 32];
+    out.copy_from_slice(bytes);
+    out
+}
+
+/// Expand a byte key into its 2-per-byte nibble path (high nibble first).
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(key.len() * 2);
+    for &b in key {
+        out.push(b >> 4);
+        out.push(b & 0x0f);
+    }
+    out
+}
+
+/// Decode the hex-prefix ("compact") encoding used by extension/leaf nodes,
+/// returning the nibble path and whether the node is a leaf.
+fn decode_compact(encoded: &[u8]) -> (Vec<u8>, bool) {
+    assert!(!encoded.is_empty(), "empty compact path");
+    let flag = encoded[0] >> 4;
+    let is_leaf = flag & 0x2 != 0;
+    let odd = flag & 0x1 != 0;
+
+    let mut nibbles = Vec::new();
+    if odd {
+        nibbles.push(encoded[0] & 0x0f);
+    }
+    for &b in &encoded[1..] {
+        nibbles.push(b >> 4);
+        nibbles.push(b & 0x0f);
+    }
+    (nibbles, is_leaf)
+}
@@ -0,0 +1,87 @@
+//! Minimal no_std RLP decoder, just enough to walk Merkle-Patricia proof nodes.
+//!
+//! We only ever decode trie nodes (2- or 17-item lists whose items are byte
+//! strings) and the account leaf (`RLP([nonce, balance, storageHash,
+//! codeHash])`), so the decoder handles only byte strings and lists (a list
+//! item that is itself a list is decoded recursively).
+
+use alloc::vec::Vec;
+
+/// A decoded RLP item: either a byte string or a list of items.
+pub enum Rlp<'a> {
+    /// A byte string payload.
+    Bytes(&'a [u8]),
+    /// A list, already split into its element items.
+    List(Vec<Rlp<'a>>),
+}
+
+impl<'a> Rlp<'a> {
+    /// Borrow the payload of a string item, panicking if this is a list.
+    pub fn bytes(&self) -> &'a [u8] {
+        match self {
+            Rlp::Bytes(b) => b,
+            Rlp::List(_) => panic!("expected RLP string, found list"),
+        }
+    }
+
+    /// Borrow the items of a list, panicking if this is a string.
+    pub fn list(&self) -> &[Rlp<'a>] {
+        match self {
+            Rlp::List(items) => items,
+            Rlp::Bytes(_) => panic!("expected RLP list, found string"),
+        }
+    }
+}
+
+/// Decode a single RLP item from the front of `input`, returning it together
+/// with the number of bytes consumed. Panics on malformed input.
+pub fn decode(input: &[u8]) -> (Rlp<'_>, usize) {
+    assert!(!input.is_empty(), "empty RLP input");
+    let prefix = input[0];
+    if prefix < 0x80 {
+        // Single byte in the [0x00, 0x7f] range is its own encoding.
+        (Rlp::Bytes(&input[0..1]), 1)
+    } else if prefix < 0xb8 {
+        // Short string: 0..55 bytes.
+        let len = (prefix - 0x80) as usize;
+        let start = 1;
+        (Rlp::Bytes(&input[start..start + len]), start + len)
+    } else if prefix < 0xc0 {
+        // Long string: length-of-length follows.
+        let ll = (prefix - 0xb7) as usize;
+        let len = be_len(&input[1..1 + ll]);
+        let start = 1 + ll;
+        (Rlp::Bytes(&input[start..start + len]), start + len)
+    } else if prefix < 0xf8 {
+        // Short list.
+        let len = (prefix - 0xc0) as usize;
+        let start = 1;
+        (Rlp::List(decode_items(&input[start..start + len])), start + len)
+    } else {
+        // Long list.
+        let ll = (prefix - 0xf7) as usize;
+        let len = be_len(&input[1..1 + ll]);
+        let start = 1 + ll;
+        (Rlp::List(decode_items(&input[start..start + len])), start + len)
+    }
+}
+
+/// Decode every item packed into a list payload.
+fn decode_items(mut payload: &[u8]) -> Vec<Rlp<'_>> {
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let (item, used) = decode(payload);
+        items.push(item);
+        payload = &payload[used..];
+    }
+    items
+}
+
+/// Interpret up to 8 big-endian bytes as a length.
+fn be_len(bytes: &[u8]) -> usize {
+    let mut len = 0usize;
+    for &b in bytes {
+        len = (len << 8) | b as usize;
+    }
+    len
+}
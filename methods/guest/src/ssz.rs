@@ -0,0 +1,170 @@
+//! The slice of SSZ merkleization the light client needs: hashing a beacon
+//! header, computing signing roots, and checking Merkle branches. All hashing
+//! uses the zkVM's accelerated sha256.
+
+use methods::light_client::{
+    BeaconBlockHeader, ExecutionPayloadHeader, SyncCommittee, DOMAIN_SYNC_COMMITTEE,
+};
+use risc0_zkvm::sha;
+
+/// sha256 of `data` via the accelerator.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    *sha::Impl::hash_bytes(data).as_bytes()
+}
+
+/// sha256 of the concatenation of two 32-byte nodes (a Merkle parent).
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 64];
+    buf[0..32].copy_from_slice(left);
+    buf[32..64].copy_from_slice(right);
+    sha256(&buf)
+}
+
+/// Merkleize `leaves` (already 32-byte chunks), padding up to the next power of
+/// two with zero nodes, and return the root.
+fn merkleize(mut leaves: Vec<[u8; 32]>) -> [u8; 32] {
+    let mut width = 1usize;
+    while width < leaves.len() {
+        width <<= 1;
+    }
+    leaves.resize(width, [0u8; 32]);
+    while leaves.len() > 1 {
+        let mut next = Vec::with_capacity(leaves.len() / 2);
+        for pair in leaves.chunks(2) {
+            next.push(hash_pair(&pair[0], &pair[1]));
+        }
+        leaves = next;
+    }
+    leaves[0]
+}
+
+/// Encode a `u64` as a 32-byte little-endian SSZ leaf.
+fn u64_leaf(value: u64) -> [u8; 32] {
+    let mut leaf = [0u8; 32];
+    leaf[0..8].copy_from_slice(&value.to_le_bytes());
+    leaf
+}
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// `hash_tree_root(BeaconBlockHeader)`.
+pub fn header_root(h: &BeaconBlockHeader) -> [u8; 32] {
+    merkleize(vec![
+        u64_leaf(h.slot),
+        u64_leaf(h.proposer_index),
+        h.parent_root,
+        h.state_root,
+        h.body_root,
+    ])
+}
+
+/// `hash_tree_root(ExecutionPayloadHeader)` for the Deneb field set. Field
+/// leaves are the value chunks; `logs_bloom` is a fixed 256-byte vector and
+/// `extra_data` a byte list (merkleized then mixed with its length).
+pub fn execution_payload_header_root(h: &ExecutionPayloadHeader) -> [u8; 32] {
+    merkleize(vec![
+        h.parent_hash,
+        bytes20_leaf(&h.fee_recipient),
+        h.state_root,
+        h.receipts_root,
+        bytes_vector_root(&h.logs_bloom),
+        h.prev_randao,
+        u64_leaf(h.block_number),
+        u64_leaf(h.gas_limit),
+        u64_leaf(h.gas_used),
+        u64_leaf(h.timestamp),
+        byte_list_root(&h.extra_data),
+        h.base_fee_per_gas,
+        h.block_hash,
+        h.transactions_root,
+        h.withdrawals_root,
+        u64_leaf(h.blob_gas_used),
+        u64_leaf(h.excess_blob_gas),
+    ])
+}
+
+/// Right-pad a 20-byte value into a 32-byte SSZ leaf.
+fn bytes20_leaf(value: &[u8; 20]) -> [u8; 32] {
+    let mut leaf = [0u8; 32];
+    leaf[0..20].copy_from_slice(value);
+    leaf
+}
+
+/// `hash_tree_root` of a fixed byte vector: pack into 32-byte chunks and
+/// merkleize (no length mix-in).
+fn bytes_vector_root(bytes: &[u8]) -> [u8; 32] {
+    merkleize(pack(bytes))
+}
+
+/// `hash_tree_root` of a byte list: merkleize the packed chunks then mix in the
+/// length, per the SSZ spec.
+fn byte_list_root(bytes: &[u8]) -> [u8; 32] {
+    let root = merkleize(pack(bytes));
+    hash_pair(&root, &u64_leaf(bytes.len() as u64))
+}
+
+/// Split `bytes` into 32-byte little-endian-padded chunks (SSZ `pack`).
+fn pack(bytes: &[u8]) -> Vec<[u8; 32]> {
+    let mut chunks = Vec::new();
+    for chunk in bytes.chunks(32) {
+        let mut leaf = [0u8; 32];
+        leaf[0..chunk.len()].copy_from_slice(chunk);
+        chunks.push(leaf);
+    }
+    if chunks.is_empty() {
+        chunks.push([0u8; 32]);
+    }
+    chunks
+}
+
+/// Digest binding a sync committee: sha256 of its pubkeys in order followed by
+/// the aggregate pubkey. Committed to the journal so a verifier can pin the
+/// trusted committee for the signature slot's period.
+pub fn sync_committee_hash(committee: &SyncCommittee) -> [u8; 32] {
+    let mut data = Vec::with_capacity(committee.pubkeys.len() * 48 + 48);
+    for pk in &committee.pubkeys {
+        data.extend_from_slice(pk);
+    }
+    data.extend_from_slice(&committee.aggregate_pubkey);
+    sha256(&data)
+}
+
+/// `compute_domain(DOMAIN_SYNC_COMMITTEE, fork_version, genesis_validators_root)`.
+pub fn sync_committee_domain(fork_version: &[u8; 4], genesis_validators_root: &[u8; 32]) -> [u8; 32] {
+    // fork_data_root = hash_tree_root(ForkData{current_version, genesis_validators_root})
+    let mut version_leaf = [0u8; 32];
+    version_leaf[0..4].copy_from_slice(fork_version);
+    let fork_data_root = hash_pair(&version_leaf, genesis_validators_root);
+
+    let mut domain = [0u8; 32];
+    domain[0..4].copy_from_slice(&DOMAIN_SYNC_COMMITTEE);
+    domain[4..32].copy_from_slice(&fork_data_root[0..28]);
+    domain
+}
+
+/// `compute_signing_root(object_root, domain)` = sha256(object_root ++ domain).
+pub fn signing_root(object_root: &[u8; 32], domain: &[u8; 32]) -> [u8; 32] {
+    hash_pair(object_root, domain)
+}
+
+/// Verify an SSZ Merkle `branch` proves `leaf` at generalized index `gindex`
+/// under `root`. The depth and the per-level left/right choice are derived from
+/// `gindex`, matching the consensus-spec `is_valid_merkle_branch`.
+pub fn verify_branch(leaf: &[u8; 32], branch: &[[u8; 32]], gindex: u64, root: &[u8; 32]) -> bool {
+    // Depth is floor(log2(gindex)); a branch of any other length is rejected.
+    if gindex == 0 || branch.len() != (63 - gindex.leading_zeros()) as usize {
+        return false;
+    }
+    let mut node = *leaf;
+    let mut index = gindex;
+    for sibling in branch {
+        if index & 1 == 1 {
+            node = hash_pair(sibling, &node);
+        } else {
+            node = hash_pair(&node, sibling);
+        }
+        index >>= 1;
+    }
+    &node == root
+}
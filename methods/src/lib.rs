@@ -3,19 +3,47 @@ use serde::{Deserialize, Serialize};
 /// Public inputs the guest receives (committed on-chain in a full system).
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct PublicInputs {
-    pub threshold: u128,        // payout threshold
+    pub threshold: [u8; 32],    // payout threshold, big-endian 256-bit
     pub commitment: [u8; 32],   // sha256(witness_blob)
+    /// Execution-layer state root at the pre block (proves pre balance + code).
+    pub state_root: [u8; 32],
+    /// Execution-layer state root at the post block (proves post balance).
+    pub post_state_root: [u8; 32],
+    pub asset: [u8; 20],
+    pub target: [u8; 20],
+    pub selector: [u8; 4],
+    /// Storage slot index of the ERC-20 `balances` mapping on the asset.
+    pub balances_slot: u64,
+    /// keccak256 of the target account's code, as committed by the state trie.
+    pub target_code_hash: [u8; 32],
+    /// keccak256 of the asset account's code, as committed by the state trie.
+    pub asset_code_hash: [u8; 32],
 }
 
 /// Public outputs the guest commits to the journal.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct PublicOutputs {
-    pub threshold: u128,
+    pub threshold: [u8; 32],
     pub loss_hi: [u8; 16],   // high 128 bits of (pre - post)
     pub loss_lo: [u8; 16],   // low  128 bits  of (pre - post)
     pub loss_ge_threshold: bool,
+    pub selector: [u8; 4],
+    pub asset: [u8; 20],
+    pub target: [u8; 20],
+    /// State roots and code hashes the proof is anchored to, echoed from the
+    /// public inputs so an external verifier can pin them. In particular
+    /// `state_root` is what a light-client receipt's derived `state_root` must
+    /// equal for the two proofs to describe the same block.
+    pub state_root: [u8; 32],
+    pub post_state_root: [u8; 32],
+    pub target_code_hash: [u8; 32],
+    pub asset_code_hash: [u8; 32],
 }
 
+pub mod u256;
+
+pub mod light_client;
+
 // Guest module to make the methods visible
 pub mod guest {
     include!(concat!(env!("OUT_DIR"), "/methods.rs"));
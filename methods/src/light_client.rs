@@ -0,0 +1,130 @@
+//! Beacon-chain light-client types.
+//!
+//! These mirror the consensus-spec `altair`–`deneb` objects a light client
+//! needs to derive an execution `state_root` from a sync-committee signature,
+//! following the Helios approach. They are shared between the host (which pulls
+//! a `LightClientUpdate` from a beacon API) and the guest (which verifies it).
+
+use serde::{Deserialize, Serialize};
+
+/// Number of validators in a sync committee.
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// `DomainType` for sync-committee signatures.
+pub const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [0x07, 0x00, 0x00, 0x00];
+
+/// Slots per sync-committee period (`EPOCHS_PER_SYNC_COMMITTEE_PERIOD * SLOTS_PER_EPOCH`
+/// = 256 * 32). The committee that signs an update is the one for the period
+/// containing its `signature_slot`.
+pub const SLOTS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256 * 32;
+
+/// Generalized index of `finalized_checkpoint.root` within `BeaconState`.
+pub const FINALIZED_ROOT_INDEX: u64 = 105;
+/// Generalized index of `execution_payload` within `BeaconBlockBody`. This is
+/// where the Capella+ `LightClientHeader.execution_branch` roots the execution
+/// payload *header*, and it is independent of the payload's own field count, so
+/// it holds across Capella/Deneb unlike a `state_root`-within-payload index.
+pub const EXECUTION_PAYLOAD_INDEX: u64 = 25;
+
+/// Minimal SSZ `BeaconBlockHeader`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BeaconBlockHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: [u8; 32],
+    pub state_root: [u8; 32],
+    pub body_root: [u8; 32],
+}
+
+/// SSZ `ExecutionPayloadHeader` (Deneb field set). The light client recomputes
+/// its `hash_tree_root` from these fields and proves that root against the
+/// beacon block body, which is what binds `state_root` to the signed
+/// checkpoint. `base_fee_per_gas` is the raw little-endian `uint256` and
+/// `logs_bloom` is the fixed 256-byte vector.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExecutionPayloadHeader {
+    pub parent_hash: [u8; 32],
+    pub fee_recipient: [u8; 20],
+    pub state_root: [u8; 32],
+    pub receipts_root: [u8; 32],
+    pub logs_bloom: Vec<u8>,
+    pub prev_randao: [u8; 32],
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub base_fee_per_gas: [u8; 32],
+    pub block_hash: [u8; 32],
+    pub transactions_root: [u8; 32],
+    pub withdrawals_root: [u8; 32],
+    pub blob_gas_used: u64,
+    pub excess_blob_gas: u64,
+}
+
+/// The committee whose signature anchors the update. Its digest is committed
+/// to the journal (`LightClientOutputs::committee_hash`), so the proof is only
+/// as trusted as this checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncCommittee {
+    pub pubkeys: Vec<[u8; 48]>,
+    pub aggregate_pubkey: [u8; 48],
+}
+
+/// Participation bitfield + aggregate BLS signature over the signing root.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncAggregate {
+    /// One bit per committee member (512 bits => 64 bytes), little-endian.
+    pub sync_committee_bits: Vec<u8>,
+    pub sync_committee_signature: [u8; 96],
+}
+
+/// A `LightClientUpdate` witness: an attested header signed by the sync
+/// committee, a finalized header proven against it, and a Merkle branch rooting
+/// the finalized block's execution payload header (which carries the state
+/// root) in its body.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LightClientUpdate {
+    pub attested_header: BeaconBlockHeader,
+    pub finalized_header: BeaconBlockHeader,
+    /// SSZ Merkle proof of `finalized_header` against `attested_header.state_root`.
+    pub finality_branch: Vec<[u8; 32]>,
+    pub sync_aggregate: SyncAggregate,
+    pub signature_slot: u64,
+    pub fork_version: [u8; 4],
+    pub genesis_validators_root: [u8; 32],
+    /// Execution payload header of the finalized block. Its `state_root` is the
+    /// value the proof ultimately anchors.
+    pub execution_payload_header: ExecutionPayloadHeader,
+    /// SSZ Merkle proof of `hash_tree_root(execution_payload_header)` against
+    /// `finalized_header.body_root` at [`EXECUTION_PAYLOAD_INDEX`].
+    pub execution_branch: Vec<[u8; 32]>,
+}
+
+/// Public inputs for the light-client guest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LightClientInputs {
+    /// The trusted sync committee for the signature slot's period.
+    pub sync_committee: SyncCommittee,
+}
+
+/// Public outputs: the trustlessly derived execution state root and the slot it
+/// corresponds to, fed into the MPT balance/code checks.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct LightClientOutputs {
+    pub state_root: [u8; 32],
+    pub slot: u64,
+    /// Execution block number `state_root` belongs to. The MPT proofs must be
+    /// fetched at this exact block — `state_root` only authenticates the trie
+    /// at the finalized execution block, not at whatever block the caller asks
+    /// `eth_getProof` for.
+    pub block_number: u64,
+    /// Digest of the sync committee that signed the update. The committee is a
+    /// private witness, so committing its hash is what lets a verifier pin the
+    /// trusted committee — without it a prover could sign with a committee of
+    /// their own and fabricate any `state_root`.
+    pub committee_hash: [u8; 32],
+    /// Slot whose period selects `committee_hash`; the verifier checks the hash
+    /// against the known committee for `signature_slot / SLOTS_PER_SYNC_COMMITTEE_PERIOD`.
+    pub signature_slot: u64,
+}
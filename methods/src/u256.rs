@@ -0,0 +1,521 @@
+//! Unsigned 256-bit integer shared by the host and the in-guest EVM.
+//!
+//! Stored as four little-endian 64-bit limbs (`limbs[0]` is least significant),
+//! modelled on rust-bitcoin's `Uint256`. Both the prover host and the zkVM
+//! guests use this single type so big-endian 256-bit math is defined in exactly
+//! one place instead of being re-derived (subtly differently) in each binary.
+//!
+//! Arithmetic comes in EVM-style `wrapping_*`, overflow-detecting `checked_*`
+//! and clamping `saturating_*` flavours; pick the one the caller needs.
+
+use core::cmp::Ordering;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct U256 {
+    limbs: [u64; 4],
+}
+
+impl U256 {
+    pub const ZERO: U256 = U256 { limbs: [0; 4] };
+    pub const MAX: U256 = U256 { limbs: [u64::MAX; 4] };
+
+    pub fn from_u64(v: u64) -> Self {
+        U256 { limbs: [v, 0, 0, 0] }
+    }
+
+    pub fn from_bool(b: bool) -> Self {
+        U256::from_u64(b as u64)
+    }
+
+    /// Parse an unsigned decimal string; `None` on a non-digit or on overflow.
+    pub fn from_dec_str(s: &str) -> Option<U256> {
+        let mut acc = U256::ZERO;
+        let ten = U256::from_u64(10);
+        for c in s.bytes() {
+            let digit = match c {
+                b'0'..=b'9' => (c - b'0') as u64,
+                _ => return None,
+            };
+            acc = acc.checked_mul(&ten)?.checked_add(&U256::from_u64(digit))?;
+        }
+        Some(acc)
+    }
+
+    /// Big-endian 20-byte address in the low bytes.
+    pub fn from_address(addr: &[u8; 20]) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[12..32].copy_from_slice(addr);
+        U256::from_be_bytes(&bytes)
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            let mut limb = [0u8; 8];
+            limb.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+            limbs[3 - i] = u64::from_be_bytes(limb);
+        }
+        U256 { limbs }
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for i in 0..4 {
+            out[i * 8..i * 8 + 8].copy_from_slice(&self.limbs[3 - i].to_be_bytes());
+        }
+        out
+    }
+
+    pub fn as_usize(&self) -> usize {
+        self.limbs[0] as usize
+    }
+
+    pub fn as_address(&self) -> [u8; 20] {
+        let bytes = self.to_be_bytes();
+        let mut out = [0u8; 20];
+        out.copy_from_slice(&bytes[12..32]);
+        out
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs == [0; 4]
+    }
+
+    pub fn wrapping_add(&self, other: &U256) -> U256 {
+        let mut limbs = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let sum = self.limbs[i] as u128 + other.limbs[i] as u128 + carry;
+            limbs[i] = sum as u64;
+            carry = sum >> 64;
+        }
+        U256 { limbs }
+    }
+
+    /// Addition that returns `None` if the true sum exceeds 256 bits.
+    pub fn checked_add(&self, other: &U256) -> Option<U256> {
+        let mut limbs = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let sum = self.limbs[i] as u128 + other.limbs[i] as u128 + carry;
+            limbs[i] = sum as u64;
+            carry = sum >> 64;
+        }
+        if carry != 0 {
+            None
+        } else {
+            Some(U256 { limbs })
+        }
+    }
+
+    /// Addition that clamps to [`U256::MAX`] on overflow.
+    pub fn saturating_add(&self, other: &U256) -> U256 {
+        self.checked_add(other).unwrap_or(U256::MAX)
+    }
+
+    pub fn wrapping_sub(&self, other: &U256) -> U256 {
+        let mut limbs = [0u64; 4];
+        let mut borrow = 0i128;
+        for i in 0..4 {
+            let diff = self.limbs[i] as i128 - other.limbs[i] as i128 - borrow;
+            if diff < 0 {
+                limbs[i] = (diff + (1i128 << 64)) as u64;
+                borrow = 1;
+            } else {
+                limbs[i] = diff as u64;
+                borrow = 0;
+            }
+        }
+        U256 { limbs }
+    }
+
+    /// Subtraction that returns `None` when `other > self`.
+    pub fn checked_sub(&self, other: &U256) -> Option<U256> {
+        if self < other {
+            None
+        } else {
+            Some(self.wrapping_sub(other))
+        }
+    }
+
+    pub fn saturating_sub(&self, other: &U256) -> U256 {
+        if self < other {
+            U256::ZERO
+        } else {
+            self.wrapping_sub(other)
+        }
+    }
+
+    pub fn wrapping_mul(&self, other: &U256) -> U256 {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..(4 - i) {
+                let idx = i + j;
+                let cur = limbs[idx] as u128
+                    + self.limbs[i] as u128 * other.limbs[j] as u128
+                    + carry;
+                limbs[idx] = cur as u64;
+                carry = cur >> 64;
+            }
+        }
+        U256 { limbs }
+    }
+
+    /// Multiplication that returns `None` if the true product exceeds 256 bits.
+    pub fn checked_mul(&self, other: &U256) -> Option<U256> {
+        // Accumulate the full 512-bit product, then reject any high limb.
+        let mut result = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let cur = result[i + j] as u128
+                    + self.limbs[i] as u128 * other.limbs[j] as u128
+                    + carry;
+                result[i + j] = cur as u64;
+                carry = cur >> 64;
+            }
+            let mut k = i + 4;
+            while carry != 0 {
+                let cur = result[k] as u128 + carry;
+                result[k] = cur as u64;
+                carry = cur >> 64;
+                k += 1;
+            }
+        }
+        if result[4..8].iter().any(|&l| l != 0) {
+            None
+        } else {
+            Some(U256 { limbs: [result[0], result[1], result[2], result[3]] })
+        }
+    }
+
+    /// Modular exponentiation with EVM `EXP` wrapping semantics.
+    pub fn pow(&self, exp: &U256) -> U256 {
+        let mut result = U256::from_u64(1);
+        let mut base = *self;
+        let mut e = *exp;
+        while !e.is_zero() {
+            if e.is_odd() {
+                result = result.wrapping_mul(&base);
+            }
+            base = base.wrapping_mul(&base);
+            e = e.shr_one();
+        }
+        result
+    }
+
+    /// `(self + other) mod m`, overflow-free; zero when `m` is zero (EVM
+    /// `ADDMOD`).
+    pub fn addmod(&self, other: &U256, m: &U256) -> U256 {
+        if m.is_zero() {
+            return U256::ZERO;
+        }
+        let a = self.rem(m);
+        let b = other.rem(m);
+        // Both operands are < m, so `m - b` is well defined and `a + b` only
+        // wraps when it reaches m — pick the branch that stays in range.
+        let m_minus_b = m.wrapping_sub(&b);
+        if a >= m_minus_b {
+            a.wrapping_sub(&m_minus_b)
+        } else {
+            a.wrapping_add(&b)
+        }
+    }
+
+    /// `(self * other) mod m` via double-and-add, zero when `m` is zero (EVM
+    /// `MULMOD`).
+    pub fn mulmod(&self, other: &U256, m: &U256) -> U256 {
+        if m.is_zero() {
+            return U256::ZERO;
+        }
+        let mut result = U256::ZERO;
+        let mut a = self.rem(m);
+        let mut b = *other;
+        while !b.is_zero() {
+            if b.is_odd() {
+                result = result.addmod(&a, m);
+            }
+            a = a.addmod(&a, m);
+            b = b.shr_one();
+        }
+        result
+    }
+
+    /// Signed division (EVM `SDIV`); zero when `other` is zero.
+    pub fn sdiv(&self, other: &U256) -> U256 {
+        if other.is_zero() {
+            return U256::ZERO;
+        }
+        let negative = self.is_negative() ^ other.is_negative();
+        let q = self.abs().div(&other.abs());
+        if negative {
+            q.wrapping_neg()
+        } else {
+            q
+        }
+    }
+
+    /// Signed remainder (EVM `SMOD`); the sign follows the dividend.
+    pub fn smod(&self, other: &U256) -> U256 {
+        if other.is_zero() {
+            return U256::ZERO;
+        }
+        let r = self.abs().rem(&other.abs());
+        if self.is_negative() {
+            r.wrapping_neg()
+        } else {
+            r
+        }
+    }
+
+    /// Signed less-than (EVM `SLT`).
+    pub fn slt(&self, other: &U256) -> bool {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => self < other,
+        }
+    }
+
+    /// Signed greater-than (EVM `SGT`).
+    pub fn sgt(&self, other: &U256) -> bool {
+        other.slt(self)
+    }
+
+    /// Two's-complement negation.
+    pub fn wrapping_neg(&self) -> U256 {
+        self.not().wrapping_add(&U256::from_u64(1))
+    }
+
+    fn is_negative(&self) -> bool {
+        self.limbs[3] >> 63 == 1
+    }
+
+    fn abs(&self) -> U256 {
+        if self.is_negative() {
+            self.wrapping_neg()
+        } else {
+            *self
+        }
+    }
+
+    fn is_odd(&self) -> bool {
+        self.limbs[0] & 1 == 1
+    }
+
+    /// Unsigned long division returning (quotient, remainder).
+    fn divrem(&self, other: &U256) -> (U256, U256) {
+        if other.is_zero() {
+            return (U256::ZERO, U256::ZERO);
+        }
+        let mut quot = U256::ZERO;
+        let mut rem = U256::ZERO;
+        for bit in (0..256).rev() {
+            rem = rem.shl_one();
+            if self.bit(bit) {
+                rem.limbs[0] |= 1;
+            }
+            if &rem >= other {
+                rem = rem.wrapping_sub(other);
+                quot.set_bit(bit);
+            }
+        }
+        (quot, rem)
+    }
+
+    pub fn div(&self, other: &U256) -> U256 {
+        self.divrem(other).0
+    }
+
+    pub fn rem(&self, other: &U256) -> U256 {
+        self.divrem(other).1
+    }
+
+    pub fn bitand(&self, other: &U256) -> U256 {
+        self.zip(other, |a, b| a & b)
+    }
+
+    pub fn bitor(&self, other: &U256) -> U256 {
+        self.zip(other, |a, b| a | b)
+    }
+
+    pub fn bitxor(&self, other: &U256) -> U256 {
+        self.zip(other, |a, b| a ^ b)
+    }
+
+    pub fn not(&self) -> U256 {
+        U256 { limbs: [!self.limbs[0], !self.limbs[1], !self.limbs[2], !self.limbs[3]] }
+    }
+
+    /// EVM `SHL`: `value << self` (self is the shift amount).
+    pub fn shl(&self, value: &U256) -> U256 {
+        let shift = self.limbs[0] as usize;
+        if self.limbs[1] != 0 || self.limbs[2] != 0 || self.limbs[3] != 0 || shift >= 256 {
+            return U256::ZERO;
+        }
+        let mut out = *value;
+        for _ in 0..shift {
+            out = out.shl_one();
+        }
+        out
+    }
+
+    /// EVM `SHR`: `value >> self` (self is the shift amount).
+    pub fn shr(&self, value: &U256) -> U256 {
+        let shift = self.limbs[0] as usize;
+        if self.limbs[1] != 0 || self.limbs[2] != 0 || self.limbs[3] != 0 || shift >= 256 {
+            return U256::ZERO;
+        }
+        let mut out = *value;
+        for _ in 0..shift {
+            out = out.shr_one();
+        }
+        out
+    }
+
+    /// EVM `BYTE`: the `self`-th byte (from the most-significant end) of `value`.
+    pub fn byte(&self, value: &U256) -> U256 {
+        let i = self.limbs[0] as usize;
+        if self.limbs[1] != 0 || self.limbs[2] != 0 || self.limbs[3] != 0 || i >= 32 {
+            return U256::ZERO;
+        }
+        U256::from_u64(value.to_be_bytes()[i] as u64)
+    }
+
+    // ---- internal bit helpers ----
+
+    fn zip(&self, other: &U256, f: impl Fn(u64, u64) -> u64) -> U256 {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            limbs[i] = f(self.limbs[i], other.limbs[i]);
+        }
+        U256 { limbs }
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.limbs[i / 64] & (1u64 << (i % 64)) != 0
+    }
+
+    fn set_bit(&mut self, i: usize) {
+        self.limbs[i / 64] |= 1u64 << (i % 64);
+    }
+
+    fn shl_one(&self) -> U256 {
+        let mut limbs = [0u64; 4];
+        let mut carry = 0u64;
+        for i in 0..4 {
+            limbs[i] = (self.limbs[i] << 1) | carry;
+            carry = self.limbs[i] >> 63;
+        }
+        U256 { limbs }
+    }
+
+    fn shr_one(&self) -> U256 {
+        let mut limbs = [0u64; 4];
+        let mut carry = 0u64;
+        for i in (0..4).rev() {
+            limbs[i] = (self.limbs[i] >> 1) | (carry << 63);
+            carry = self.limbs[i] & 1;
+        }
+        U256 { limbs }
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for i in (0..4).rev() {
+            match self.limbs[i].cmp(&other.limbs[i]) {
+                Ordering::Equal => continue,
+                non_eq => return non_eq,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2^256 - 1 in decimal, for the parsing boundary checks.
+    const MAX_DEC: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    fn u(v: u64) -> U256 {
+        U256::from_u64(v)
+    }
+
+    #[test]
+    fn be_bytes_round_trip() {
+        let mut bytes = [0u8; 32];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (i * 7 + 1) as u8;
+        }
+        assert_eq!(U256::from_be_bytes(&bytes).to_be_bytes(), bytes);
+    }
+
+    #[test]
+    fn from_dec_str_parses_and_rejects() {
+        assert_eq!(U256::from_dec_str("0"), Some(U256::ZERO));
+        assert_eq!(U256::from_dec_str("255"), Some(u(255)));
+        assert_eq!(U256::from_dec_str(MAX_DEC), Some(U256::MAX));
+        assert!(U256::from_dec_str("12x3").is_none());
+        // 2^256 overflows.
+        assert!(U256::from_dec_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn checked_add_detects_overflow() {
+        assert_eq!(u(2).checked_add(&u(3)), Some(u(5)));
+        assert_eq!(U256::MAX.checked_add(&u(1)), None);
+        assert_eq!(U256::MAX.saturating_add(&u(1)), U256::MAX);
+        // Carry propagates across the limb boundary.
+        let limb = u(u64::MAX);
+        assert_eq!(limb.checked_add(&u(1)), U256::from_dec_str("18446744073709551616"));
+    }
+
+    #[test]
+    fn checked_sub_detects_underflow() {
+        assert_eq!(u(5).checked_sub(&u(3)), Some(u(2)));
+        assert_eq!(u(0).checked_sub(&u(1)), None);
+        assert_eq!(u(0).saturating_sub(&u(1)), U256::ZERO);
+    }
+
+    #[test]
+    fn checked_mul_detects_overflow() {
+        assert_eq!(u(6).checked_mul(&u(7)), Some(u(42)));
+        // 2^128 * 2^128 = 2^256, which does not fit.
+        let two_128 = u(128).shl(&u(1));
+        assert_eq!(two_128.checked_mul(&two_128), None);
+        // 2^128 * 2^127 = 2^255, which does.
+        let two_127 = u(127).shl(&u(1));
+        assert_eq!(two_128.checked_mul(&two_127), Some(u(255).shl(&u(1))));
+    }
+
+    #[test]
+    fn div_and_rem() {
+        assert_eq!(u(100).div(&u(7)), u(14));
+        assert_eq!(u(100).rem(&u(7)), u(2));
+        // Division by zero yields zero, matching EVM DIV/MOD semantics.
+        assert_eq!(u(100).div(&U256::ZERO), U256::ZERO);
+    }
+
+    #[test]
+    fn shift_semantics() {
+        // `shl`/`shr` shift the argument by `self`; SHL(shift 4, value 1) == 16.
+        assert_eq!(u(4).shl(&u(1)), u(16));
+        assert_eq!(u(1).shr(&u(8)), u(4));
+        // Shifting by >= 256 clears the value.
+        assert_eq!(u(256).shl(&u(1)), U256::ZERO);
+    }
+}